@@ -0,0 +1,52 @@
+//! Benchmark of tree rendering, run with `cargo bench`.
+
+use antex::{leaf, node, Color, ColorMode, StyledText, TreeNode};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Builds a tree with `groups` nodes having `leaves` leaves each.
+fn wide_tree(groups: usize, leaves: usize) -> TreeNode {
+  let cm = ColorMode::On;
+  let mut root = node(Color::Yellow, cm).line().s("root").end();
+  for i in 0..groups {
+    let mut group = node(Color::Cyan, cm).line().s("group ").s(i).end();
+    for j in 0..leaves {
+      group.add_child(leaf(cm).line().green().s("leaf ").s(j).clear().end().line().s("second line").end().end());
+    }
+    root.add_child(group.end());
+  }
+  root.end()
+}
+
+/// Builds a tree being a single chain of nodes.
+fn deep_tree(depth: usize) -> TreeNode {
+  let cm = ColorMode::On;
+  let mut root = leaf(cm).line().s("leaf").end().end();
+  for i in 0..depth {
+    root = node(Color::Magenta, cm).line().s("node ").s(i).end().child(root).end();
+  }
+  root
+}
+
+/// Renders the tree specified number of times and reports the average time.
+fn bench(name: &str, tree: &TreeNode, iterations: u32) {
+  let mut output = String::new();
+  let _ = tree.write(&mut output);
+  let size = output.len();
+  let start = Instant::now();
+  for _ in 0..iterations {
+    output.clear();
+    let _ = black_box(tree).write(&mut output);
+    black_box(&output);
+  }
+  let elapsed: Duration = start.elapsed() / iterations;
+  println!("{name:<24} {size:>12} bytes {elapsed:>12.3?}/iter");
+}
+
+fn main() {
+  bench("wide 100 x 1000", &wide_tree(100, 1000), 10);
+  bench("deep 1000", &deep_tree(1000), 10);
+  let tree = deep_tree(5000);
+  bench("deep 5000", &tree, 2);
+  tree.dispose();
+}
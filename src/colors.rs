@@ -7,6 +7,7 @@ pub type RgbColor = (u8, u8, u8);
 
 /// Type representing a color value in several formats.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
   Black,
   Red,
@@ -42,8 +43,64 @@ impl From<RgbColor> for Color {
   }
 }
 
+/// Basic and bright colors of the 256 color palette, as in xterm.
+const BASIC_COLORS: [RgbColor; 16] = [
+  (0, 0, 0),
+  (205, 0, 0),
+  (0, 205, 0),
+  (205, 205, 0),
+  (0, 0, 238),
+  (205, 0, 205),
+  (0, 205, 205),
+  (229, 229, 229),
+  (127, 127, 127),
+  (255, 0, 0),
+  (0, 255, 0),
+  (255, 255, 0),
+  (92, 92, 255),
+  (255, 0, 255),
+  (0, 255, 255),
+  (255, 255, 255),
+];
+
+/// Intensity levels of the 6x6x6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+  /// Returns the RGB value of the color, as displayed with the xterm 256 color palette.
+  pub fn to_rgb(&self) -> RgbColor {
+    match *self {
+      Color::Black => BASIC_COLORS[0],
+      Color::Red => BASIC_COLORS[1],
+      Color::Green => BASIC_COLORS[2],
+      Color::Yellow => BASIC_COLORS[3],
+      Color::Blue => BASIC_COLORS[4],
+      Color::Magenta => BASIC_COLORS[5],
+      Color::Cyan => BASIC_COLORS[6],
+      Color::White => BASIC_COLORS[7],
+      Color::Long(n) if n < 16 => BASIC_COLORS[n as usize],
+      Color::Long(n) if n < 232 => {
+        let n = n - 16;
+        (CUBE_LEVELS[(n / 36) as usize], CUBE_LEVELS[(n / 6 % 6) as usize], CUBE_LEVELS[(n % 6) as usize])
+      }
+      Color::Long(n) => {
+        let level = 8 + (n - 232) * 10;
+        (level, level, level)
+      }
+      Color::Rgb(rgb) => rgb,
+    }
+  }
+
+  /// Returns the color in hexadecimal notation used by HTML and CSS, like `#cd0000`.
+  pub fn to_hex(&self) -> String {
+    let (r, g, b) = self.to_rgb();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+  }
+}
+
 /// Color mode to switch terminal colouring `ON` or `OFF`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorMode {
   /// Switch colouring **on**.
   On,
@@ -0,0 +1,237 @@
+//! # Exporting trees to other formats
+
+use crate::colors::Color;
+use crate::style::Style;
+use crate::text::{strip_escapes, Text};
+use crate::traverse::TreeVisitor;
+use crate::tree::TreeNode;
+use std::fmt::Write;
+
+impl TreeNode {
+  /// Returns the tree as a JSON document.
+  ///
+  /// Nodes are objects with `title`, `color` and `children`, leaves are objects with `lines`.
+  /// Every text has its plain `text` and the list of its styled `spans`,
+  /// span attributes equal to defaults are omitted, colors are written as `#rrggbb`.
+  pub fn to_json(&self) -> String {
+    let mut exporter = JsonExporter { output: String::new() };
+    self.walk(&mut exporter);
+    exporter.output
+  }
+
+  /// Returns the tree as a Graphviz DOT digraph.
+  ///
+  /// The color of a node is written as the `color` attribute of the node and of edges to its children.
+  pub fn to_dot(&self) -> String {
+    let mut output = "digraph tree {\n  node [shape=box, fontname=\"monospace\"];\n".to_string();
+    walk_with_parents(self, |node, index, parent| {
+      let label = node.label().replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+      match node {
+        TreeNode::Node(_, _, color, _) => {
+          let _ = writeln!(output, "  n{} [label=\"{}\", color=\"{}\"];", index, label, color.to_hex());
+        }
+        TreeNode::Leaf(_) => {
+          let _ = writeln!(output, "  n{} [label=\"{}\"];", index, label);
+        }
+      }
+      if let Some((parent_index, color)) = parent {
+        let _ = writeln!(output, "  n{} -> n{} [color=\"{}\"];", parent_index, index, color.to_hex());
+      }
+    });
+    output.push_str("}\n");
+    output
+  }
+
+  /// Returns the tree as a Mermaid `graph TD` block.
+  ///
+  /// The color of a node is written as the stroke of the node and of links to its children.
+  pub fn to_mermaid(&self) -> String {
+    let mut output = "graph TD\n".to_string();
+    let mut styles = String::new();
+    walk_with_parents(self, |node, index, parent| {
+      let label = node.label().replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;").replace('\n', "<br/>");
+      match parent {
+        Some((parent_index, color)) => {
+          let _ = writeln!(output, "  n{} --> n{}[\"{}\"]", parent_index, index, label);
+          // links are numbered in order of declaration, the root node has no link
+          let _ = writeln!(styles, "  linkStyle {} stroke:{}", index - 1, color.to_hex());
+        }
+        None => {
+          let _ = writeln!(output, "  n{}[\"{}\"]", index, label);
+        }
+      }
+      if let TreeNode::Node(_, _, color, _) = node {
+        let _ = writeln!(styles, "  style n{} stroke:{}", index, color.to_hex());
+      }
+    });
+    output.push_str(&styles);
+    output
+  }
+
+  /// Returns the tree as an indented YAML-like outline.
+  ///
+  /// Nodes are written as `- title:` items followed by their children indented by two spaces,
+  /// every line of a leaf is written as a `- line` item, texts are written as plain text.
+  pub fn to_outline(&self) -> String {
+    let mut exporter = OutlineExporter { output: String::new() };
+    self.walk(&mut exporter);
+    exporter.output
+  }
+}
+
+/// Visits nodes in pre-order, passing each node with its pre-order index,
+/// and the index and color of its parent node, to the function.
+fn walk_with_parents(tree: &TreeNode, f: impl FnMut(&TreeNode, usize, Option<(usize, Color)>)) {
+  let mut visitor = ParentTracker { count: 0, ancestors: vec![], f };
+  tree.walk(&mut visitor);
+}
+
+/// Visitor tracking indexes and colors of ancestors of the visited node.
+struct ParentTracker<F> {
+  /// Number of nodes entered so far.
+  count: usize,
+  /// Indexes of entered nodes not left yet, with colors of nodes.
+  ancestors: Vec<(usize, Option<Color>)>,
+  /// Function called when entering a node.
+  f: F,
+}
+
+impl<F: FnMut(&TreeNode, usize, Option<(usize, Color)>)> TreeVisitor for ParentTracker<F> {
+  fn enter(&mut self, node: &TreeNode, _depth: usize, _path: &[usize]) {
+    let parent = self.ancestors.last().and_then(|(index, color)| color.map(|color| (*index, color)));
+    (self.f)(node, self.count, parent);
+    let color = match node {
+      TreeNode::Node(_, _, color, _) => Some(*color),
+      TreeNode::Leaf(_) => None,
+    };
+    self.ancestors.push((self.count, color));
+    self.count += 1;
+  }
+
+  fn leave(&mut self, _node: &TreeNode, _depth: usize, _path: &[usize]) {
+    self.ancestors.pop();
+  }
+}
+
+/// Visitor writing nodes as JSON objects.
+struct JsonExporter {
+  output: String,
+}
+
+impl TreeVisitor for JsonExporter {
+  fn enter(&mut self, node: &TreeNode, _depth: usize, path: &[usize]) {
+    if path.last().is_some_and(|index| *index > 0) {
+      self.output.push(',');
+    }
+    match node {
+      TreeNode::Node(title, _, color, _) => {
+        self.output.push_str("{\"title\":");
+        self.write_text(title);
+        let _ = write!(self.output, ",\"color\":\"{}\",\"children\":[", color.to_hex());
+      }
+      TreeNode::Leaf(lines) => {
+        self.output.push_str("{\"lines\":[");
+        for (i, line) in lines.iter().enumerate() {
+          if i > 0 {
+            self.output.push(',');
+          }
+          self.write_text(line);
+        }
+        self.output.push(']');
+      }
+    }
+  }
+
+  fn leave(&mut self, node: &TreeNode, _depth: usize, _path: &[usize]) {
+    if node.is_node() {
+      self.output.push(']');
+    }
+    self.output.push('}');
+  }
+}
+
+impl JsonExporter {
+  /// Writes a text as an object with its plain text and styled spans.
+  fn write_text(&mut self, text: &Text) {
+    self.output.push_str("{\"text\":");
+    write_json_string(&mut self.output, &text.plain());
+    self.output.push_str(",\"spans\":[");
+    for (i, span) in text.spans().iter().enumerate() {
+      if i > 0 {
+        self.output.push(',');
+      }
+      self.output.push_str("{\"text\":");
+      write_json_string(&mut self.output, &strip_escapes(&span.content));
+      write_style(&mut self.output, &span.style);
+      self.output.push('}');
+    }
+    self.output.push_str("]}");
+  }
+}
+
+/// Visitor writing nodes as items of an indented outline.
+struct OutlineExporter {
+  output: String,
+}
+
+impl TreeVisitor for OutlineExporter {
+  fn enter(&mut self, node: &TreeNode, depth: usize, _path: &[usize]) {
+    match node {
+      TreeNode::Node(title, _, _, _) => self.write_item(depth, &format!("{}:", title.plain())),
+      TreeNode::Leaf(lines) => {
+        for line in lines {
+          self.write_item(depth, &line.plain());
+        }
+      }
+    }
+  }
+}
+
+impl OutlineExporter {
+  /// Writes an item indented by depth, continuation lines are aligned with the first line of the item.
+  fn write_item(&mut self, depth: usize, content: &str) {
+    let indent = "  ".repeat(depth);
+    for (i, line) in content.split('\n').enumerate() {
+      let marker = if i == 0 { "- " } else { "  " };
+      let _ = writeln!(self.output, "{}{}{}", indent, marker, line);
+    }
+  }
+}
+
+/// Writes the attributes of a style which differ from defaults as JSON object members.
+fn write_style(output: &mut String, style: &Style) {
+  if let Some(fg) = style.fg {
+    let _ = write!(output, ",\"fg\":\"{}\"", fg.to_hex());
+  }
+  if let Some(bg) = style.bg {
+    let _ = write!(output, ",\"bg\":\"{}\"", bg.to_hex());
+  }
+  if style.bold {
+    output.push_str(",\"bold\":true");
+  }
+  if style.italic {
+    output.push_str(",\"italic\":true");
+  }
+  if style.underline {
+    output.push_str(",\"underline\":true");
+  }
+}
+
+/// Writes a string as a quoted JSON string.
+fn write_json_string(output: &mut String, s: &str) {
+  output.push('"');
+  for ch in s.chars() {
+    match ch {
+      '"' => output.push_str("\\\""),
+      '\\' => output.push_str("\\\\"),
+      '\n' => output.push_str("\\n"),
+      '\r' => output.push_str("\\r"),
+      '\t' => output.push_str("\\t"),
+      ch if ch.is_control() => {
+        let _ = write!(output, "\\u{:04x}", ch as u32);
+      }
+      ch => output.push(ch),
+    }
+  }
+  output.push('"');
+}
@@ -0,0 +1,279 @@
+//! # Tree of a directory
+
+use crate::colors::{Color, ColorMode};
+use crate::text::{StyledText, Text};
+use crate::tree::TreeNode;
+use std::fs::{self, DirEntry, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Builds a [TreeNode] listing the content of a directory with default options.
+pub fn dir_tree(path: impl AsRef<Path>, cm: ColorMode) -> io::Result<TreeNode> {
+  DirTreeBuilder::new(cm).build(path)
+}
+
+/// Builder of a [TreeNode] listing the content of a directory, like the `tree` command.
+#[derive(Debug, Clone)]
+pub struct DirTreeBuilder {
+  /// Color mode.
+  cm: ColorMode,
+  /// Maximum depth of listed entries, entries of the root directory have depth one.
+  max_depth: Option<usize>,
+  /// Flag indicating if hidden entries are listed.
+  hidden: bool,
+  /// Patterns of file names to be listed, all files are listed when empty.
+  include: Vec<String>,
+  /// Patterns of file and directory names to be skipped.
+  exclude: Vec<String>,
+  /// Flag indicating if symbolic links to directories are traversed.
+  follow_symlinks: bool,
+  /// Flag indicating if directories are listed before files.
+  dirs_first: bool,
+  /// Color of tree guides.
+  guide_color: Color,
+  /// Color of directory names.
+  dir_color: Color,
+  /// Color of executable file names.
+  exec_color: Color,
+  /// Color of symbolic link names.
+  symlink_color: Color,
+}
+
+impl DirTreeBuilder {
+  pub fn new(cm: ColorMode) -> Self {
+    Self {
+      cm,
+      max_depth: None,
+      hidden: false,
+      include: vec![],
+      exclude: vec![],
+      follow_symlinks: false,
+      dirs_first: false,
+      guide_color: Color::White,
+      dir_color: Color::Blue,
+      exec_color: Color::Green,
+      symlink_color: Color::Cyan,
+    }
+  }
+
+  /// Sets the maximum depth of listed entries, entries of the root directory have depth one,
+  /// with depth zero only the root directory is listed.
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Lists hidden entries, with names starting with a dot.
+  pub fn hidden(mut self, hidden: bool) -> Self {
+    self.hidden = hidden;
+    self
+  }
+
+  /// Lists only files with names matching the glob pattern, may be called many times.
+  ///
+  /// Patterns support `*`, `?` and character classes like `[a-z]` or `[!0-9]`.
+  pub fn include(mut self, pattern: &str) -> Self {
+    self.include.push(pattern.to_string());
+    self
+  }
+
+  /// Skips files and directories with names matching the glob pattern, may be called many times.
+  pub fn exclude(mut self, pattern: &str) -> Self {
+    self.exclude.push(pattern.to_string());
+    self
+  }
+
+  /// Traverses symbolic links to directories, links creating cycles are never traversed.
+  pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+    self.follow_symlinks = follow_symlinks;
+    self
+  }
+
+  /// Lists directories before files.
+  pub fn dirs_first(mut self, dirs_first: bool) -> Self {
+    self.dirs_first = dirs_first;
+    self
+  }
+
+  /// Sets the color of tree guides.
+  pub fn guide_color(mut self, color: Color) -> Self {
+    self.guide_color = color;
+    self
+  }
+
+  /// Sets the color of directory names.
+  pub fn dir_color(mut self, color: Color) -> Self {
+    self.dir_color = color;
+    self
+  }
+
+  /// Sets the color of executable file names.
+  pub fn exec_color(mut self, color: Color) -> Self {
+    self.exec_color = color;
+    self
+  }
+
+  /// Sets the color of symbolic link names.
+  pub fn symlink_color(mut self, color: Color) -> Self {
+    self.symlink_color = color;
+    self
+  }
+
+  /// Builds the tree of specified directory.
+  ///
+  /// Errors reading the root directory are returned, errors reading
+  /// subdirectories are listed in the tree in place of their content.
+  pub fn build(&self, path: impl AsRef<Path>) -> io::Result<TreeNode> {
+    let path = path.as_ref();
+    let title = Text::new(self.cm).color(self.dir_color).bold().s(path.display()).clear();
+    let mut ancestors = vec![fs::canonicalize(path)?];
+    let children = if self.max_depth == Some(0) { vec![] } else { self.children(path, 1, &mut ancestors)? };
+    Ok(TreeNode::Node(title, children, self.guide_color, self.cm))
+  }
+
+  /// Builds nodes of entries in specified directory.
+  fn children(&self, path: &Path, depth: usize, ancestors: &mut Vec<PathBuf>) -> io::Result<Vec<TreeNode>> {
+    let mut entries = vec![];
+    for entry in fs::read_dir(path)? {
+      let entry = entry?;
+      let name = entry.file_name().to_string_lossy().to_string();
+      let metadata = entry.metadata()?;
+      let is_dir = self.is_dir(&entry, &metadata);
+      if (!self.hidden && name.starts_with('.'))
+        || self.exclude.iter().any(|pattern| glob_match(pattern, &name))
+        || (!is_dir && !self.include.is_empty() && !self.include.iter().any(|pattern| glob_match(pattern, &name)))
+      {
+        continue;
+      }
+      entries.push((entry, metadata, name, is_dir));
+    }
+    entries.sort_by(|(_, _, a_name, a_dir), (_, _, b_name, b_dir)| {
+      if self.dirs_first {
+        b_dir.cmp(a_dir).then_with(|| a_name.cmp(b_name))
+      } else {
+        a_name.cmp(b_name)
+      }
+    });
+    let mut children = vec![];
+    for (entry, metadata, name, is_dir) in entries {
+      children.push(self.entry_node(&entry, &metadata, &name, is_dir, depth, ancestors));
+    }
+    Ok(children)
+  }
+
+  /// Builds the node of a single directory entry.
+  fn entry_node(&self, entry: &DirEntry, metadata: &Metadata, name: &str, is_dir: bool, depth: usize, ancestors: &mut Vec<PathBuf>) -> TreeNode {
+    let mut title = Text::new(self.cm);
+    if metadata.file_type().is_symlink() {
+      title = title.color(self.symlink_color).s(name).clear();
+      if let Ok(target) = fs::read_link(entry.path()) {
+        title = title.s(" -> ").s(target.display());
+      }
+    } else if is_dir {
+      title = title.color(self.dir_color).bold().s(name).clear();
+    } else if is_executable(metadata) {
+      title = title.color(self.exec_color).bold().s(name).clear();
+    } else {
+      title = title.s(name);
+    }
+    if !is_dir {
+      return TreeNode::Leaf(vec![title]);
+    }
+    if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+      return TreeNode::Node(title, vec![], self.guide_color, self.cm);
+    }
+    let path = entry.path();
+    let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    if ancestors.contains(&canonical) {
+      return TreeNode::Node(title, vec![], self.guide_color, self.cm);
+    }
+    ancestors.push(canonical);
+    let children = self.children(&path, depth + 1, ancestors);
+    ancestors.pop();
+    let children = children.unwrap_or_else(|e| vec![TreeNode::Leaf(vec![Text::new(self.cm).red().s('[').s(e).s(']').clear()])]);
+    TreeNode::Node(title, children, self.guide_color, self.cm)
+  }
+
+  /// Returns `true` when the entry is a directory to be traversed.
+  fn is_dir(&self, entry: &DirEntry, metadata: &Metadata) -> bool {
+    if metadata.file_type().is_symlink() {
+      self.follow_symlinks && fs::metadata(entry.path()).is_ok_and(|target| target.is_dir())
+    } else {
+      metadata.is_dir()
+    }
+  }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &Metadata) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &Metadata) -> bool {
+  false
+}
+
+/// Matches a name against a glob pattern with `*`, `?` and character classes.
+fn glob_match(pattern: &str, name: &str) -> bool {
+  let pattern = pattern.chars().collect::<Vec<char>>();
+  let name = name.chars().collect::<Vec<char>>();
+  let (mut p, mut n) = (0, 0);
+  // position in the pattern after the last `*` and position in the name matched by it
+  let mut backtrack = None;
+  while n < name.len() {
+    let matched = match pattern.get(p) {
+      Some('*') => {
+        backtrack = Some((p + 1, n));
+        p += 1;
+        continue;
+      }
+      Some('?') => Some(p + 1),
+      Some('[') => match_class(&pattern, p, name[n]),
+      Some(ch) if *ch == name[n] => Some(p + 1),
+      _ => None,
+    };
+    match (matched, backtrack) {
+      (Some(next), _) => {
+        p = next;
+        n += 1;
+      }
+      (None, Some((star_p, star_n))) => {
+        backtrack = Some((star_p, star_n + 1));
+        p = star_p;
+        n = star_n + 1;
+      }
+      (None, None) => return false,
+    }
+  }
+  pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+/// Matches a character against a character class starting at position `start` in the pattern.
+///
+/// Returns the position in the pattern after the class when the character matches.
+fn match_class(pattern: &[char], start: usize, ch: char) -> Option<usize> {
+  let mut i = start + 1;
+  let negated = matches!(pattern.get(i), Some('!') | Some('^'));
+  if negated {
+    i += 1;
+  }
+  let mut matched = false;
+  let mut first = true;
+  while i < pattern.len() && (first || pattern[i] != ']') {
+    first = false;
+    if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|end| *end != ']') {
+      matched |= pattern[i] <= ch && ch <= pattern[i + 2];
+      i += 3;
+    } else {
+      matched |= pattern[i] == ch;
+      i += 1;
+    }
+  }
+  if i >= pattern.len() {
+    // unterminated class matches the bracket literally
+    return (ch == '[').then_some(start + 1);
+  }
+  (matched != negated).then_some(i + 1)
+}
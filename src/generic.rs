@@ -0,0 +1,144 @@
+//! # Generic tree
+
+use crate::colors::Color;
+use crate::text::Text;
+use crate::tree::TreeNode;
+use std::cmp::Ordering;
+
+/// Maps values of a [Tree] to styled text when the tree is rendered.
+///
+/// Values of nodes with child nodes become titles of [TreeNode::Node],
+/// values of nodes without child nodes become lines of [TreeNode::Leaf].
+/// Any function `Fn(&T) -> Text` is a renderer drawing guides in white.
+pub trait TreeRenderer<T> {
+  /// Returns the title of a node with child nodes.
+  fn title(&self, value: &T) -> Text;
+
+  /// Returns the lines of a node without child nodes, the title by default.
+  fn lines(&self, value: &T) -> Vec<Text> {
+    vec![self.title(value)]
+  }
+
+  /// Returns the color of guides drawn to child nodes of a node.
+  fn color(&self, _value: &T) -> Color {
+    Color::White
+  }
+}
+
+impl<T, F> TreeRenderer<T> for F
+where
+  F: Fn(&T) -> Text,
+{
+  fn title(&self, value: &T) -> Text {
+    self(value)
+  }
+}
+
+/// Tree holding domain values, rendered with a [TreeRenderer].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tree<T> {
+  /// Value of the node.
+  pub value: T,
+  /// Child nodes.
+  pub children: Vec<Tree<T>>,
+}
+
+impl<T> Tree<T> {
+  /// Creates a tree node without child nodes.
+  pub fn new(value: T) -> Self {
+    Self { value, children: vec![] }
+  }
+
+  /// Appends a child node.
+  pub fn child(mut self, child: Tree<T>) -> Self {
+    self.children.push(child);
+    self
+  }
+
+  /// Appends a child node.
+  pub fn add_child(&mut self, child: Tree<T>) {
+    self.children.push(child);
+  }
+
+  /// Returns `true` when the node has no child nodes.
+  pub fn is_leaf(&self) -> bool {
+    self.children.is_empty()
+  }
+
+  /// Drops the tree iteratively, for trees too deep to be dropped recursively.
+  pub fn dispose(self) {
+    let mut stack = vec![self];
+    while let Some(mut node) = stack.pop() {
+      stack.append(&mut node.children);
+    }
+  }
+
+  /// Renders the tree into styled [TreeNode] using specified renderer.
+  pub fn render<R: TreeRenderer<T> + ?Sized>(&self, renderer: &R) -> TreeNode {
+    self.fold(|tree, children| {
+      if tree.children.is_empty() {
+        TreeNode::Leaf(renderer.lines(&tree.value))
+      } else {
+        let title = renderer.title(&tree.value);
+        let cm = title.color_mode();
+        TreeNode::Node(title, children, renderer.color(&tree.value), cm)
+      }
+    })
+  }
+
+  /// Returns a tree with values mapped by specified function.
+  pub fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Tree<U> {
+    self.fold(|tree, children| Tree { value: f(&tree.value), children })
+  }
+
+  /// Returns a copy of the tree with only nodes whose values match the predicate, and their ancestors.
+  ///
+  /// Returns `None` when no node in the tree matches.
+  pub fn filter<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<Tree<T>>
+  where
+    T: Clone,
+  {
+    self.fold(|tree, children: Vec<Option<Tree<T>>>| {
+      let children = children.into_iter().flatten().collect::<Vec<Tree<T>>>();
+      if predicate(&tree.value) || !children.is_empty() {
+        Some(Tree {
+          value: tree.value.clone(),
+          children,
+        })
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Sorts child nodes of all nodes in the tree by their values.
+  pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+    let mut stack = vec![self];
+    while let Some(tree) = stack.pop() {
+      tree.children.sort_by(|a, b| compare(&a.value, &b.value));
+      stack.extend(tree.children.iter_mut());
+    }
+  }
+
+  /// Folds the tree bottom-up without recursion.
+  ///
+  /// The function is called for every node with the results already collected for its child nodes.
+  fn fold<U, F: FnMut(&Tree<T>, Vec<U>) -> U>(&self, mut f: F) -> U {
+    let mut stack: Vec<(&Tree<T>, usize, Vec<U>)> = vec![(self, 0, vec![])];
+    while let Some((tree, next, _)) = stack.last_mut() {
+      let tree: &Tree<T> = tree;
+      if let Some(child) = tree.children.get(*next) {
+        *next += 1;
+        stack.push((child, 0, vec![]));
+      } else if let Some((tree, _, children)) = stack.pop() {
+        let folded = f(tree, children);
+        match stack.last_mut() {
+          Some((_, _, parent_children)) => parent_children.push(folded),
+          None => return folded,
+        }
+      }
+    }
+    unreachable!("the root node is always folded last")
+  }
+}
@@ -0,0 +1,126 @@
+//! # HTML output
+
+use crate::colors::ColorMode;
+use crate::options::RenderOptions;
+use crate::style::Style;
+use crate::text::{pieces, Piece, Text};
+use crate::tree::TreeNode;
+use std::fmt;
+
+impl Text {
+  /// Returns the text as HTML, styled spans become `<span>` elements with inline styles.
+  ///
+  /// Styles are kept whatever the color mode of the text is.
+  pub fn to_html(&self) -> String {
+    let mut html = String::new();
+    let mut writer = HtmlWriter::new(&mut html);
+    let result = self.write(&mut writer, ColorMode::On);
+    let _ = result.and_then(|_| writer.close());
+    html
+  }
+}
+
+impl TreeNode {
+  /// Returns the tree as HTML, rendered in a `<pre>` block, see [Text::to_html].
+  pub fn to_html(&self) -> String {
+    self.to_html_with(&RenderOptions::default())
+  }
+
+  /// Returns the tree as HTML, rendered in a `<pre>` block using specified rendering options.
+  pub fn to_html_with(&self, options: &RenderOptions) -> String {
+    let options = options.clone().color_mode(ColorMode::On);
+    let mut html = "<pre>".to_string();
+    let mut writer = HtmlWriter::new(&mut html);
+    let result = self.write_with(&mut writer, &options);
+    let _ = result.and_then(|_| writer.close());
+    html.push_str("</pre>");
+    html
+  }
+}
+
+/// Adapter converting text with escape sequences into HTML.
+///
+/// SGR sequences set the style of `<span>` elements wrapping the following text,
+/// other escape sequences are dropped and special characters are escaped.
+struct HtmlWriter<'a> {
+  /// Underlying output.
+  inner: &'a mut dyn fmt::Write,
+  /// Style set by escape sequences written so far.
+  style: Style,
+  /// Style of the open `<span>` element.
+  open: Option<Style>,
+}
+
+impl<'a> HtmlWriter<'a> {
+  fn new(inner: &'a mut dyn fmt::Write) -> Self {
+    Self {
+      inner,
+      style: Style::default(),
+      open: None,
+    }
+  }
+
+  /// Writes visible text, `<span>` elements are opened when the style changes and closed at line ends.
+  fn write_text(&mut self, s: &str) -> fmt::Result {
+    for ch in s.chars() {
+      if ch == '\n' {
+        self.close()?;
+      } else if self.open != Some(self.style) {
+        self.close()?;
+        if !self.style.is_plain() {
+          write!(self.inner, "<span style=\"{}\">", css(&self.style))?;
+          self.open = Some(self.style);
+        }
+      }
+      match ch {
+        '&' => self.inner.write_str("&amp;")?,
+        '<' => self.inner.write_str("&lt;")?,
+        '>' => self.inner.write_str("&gt;")?,
+        '"' => self.inner.write_str("&quot;")?,
+        ch => self.inner.write_char(ch)?,
+      }
+    }
+    Ok(())
+  }
+
+  /// Closes the open `<span>` element.
+  fn close(&mut self) -> fmt::Result {
+    if self.open.take().is_some() {
+      self.inner.write_str("</span>")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Write for HtmlWriter<'_> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for piece in pieces(s) {
+      match piece {
+        Piece::Text(text) => self.write_text(text)?,
+        Piece::Sgr(params) => self.style.apply_sgr(params),
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Returns inline CSS declarations of a style.
+fn css(style: &Style) -> String {
+  let mut declarations = vec![];
+  if let Some(fg) = style.fg {
+    declarations.push(format!("color:{}", fg.to_hex()));
+  }
+  if let Some(bg) = style.bg {
+    declarations.push(format!("background-color:{}", bg.to_hex()));
+  }
+  if style.bold {
+    declarations.push("font-weight:bold".to_string());
+  }
+  if style.italic {
+    declarations.push("font-style:italic".to_string());
+  }
+  if style.underline {
+    declarations.push("text-decoration:underline".to_string());
+  }
+  declarations.join(";")
+}
@@ -0,0 +1,57 @@
+//! # Tree of a JSON value
+
+use crate::colors::{Color, ColorMode};
+use crate::text::{StyledText, Text};
+use crate::tree::TreeNode;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Builds a [TreeNode] displaying a JSON value.
+///
+/// Objects and arrays become nodes, scalars become leaves colored by type:
+/// strings green, numbers cyan, booleans yellow and null gray.
+pub fn json_tree(value: &Value, cm: ColorMode) -> TreeNode {
+  match value {
+    Value::Object(map) => object(Text::new(cm), map, cm),
+    Value::Array(items) => array(Text::new(cm), items, cm),
+    _ => TreeNode::Leaf(vec![scalar(Text::new(cm), value)]),
+  }
+}
+
+/// Builds a [TreeNode] displaying any serializable value, see [json_tree].
+pub fn serde_tree<T: Serialize + ?Sized>(value: &T, cm: ColorMode) -> serde_json::Result<TreeNode> {
+  Ok(json_tree(&serde_json::to_value(value)?, cm))
+}
+
+/// Builds the node of a labeled value, the label is already written to `text`.
+fn entry(text: Text, value: &Value, cm: ColorMode) -> TreeNode {
+  match value {
+    Value::Object(map) if !map.is_empty() => object(text.space(), map, cm),
+    Value::Array(items) if !items.is_empty() => array(text.space(), items, cm),
+    _ => TreeNode::Leaf(vec![scalar(text.colon().space(), value)]),
+  }
+}
+
+/// Builds the node of an object, the title is followed by the number of members.
+fn object(text: Text, map: &Map<String, Value>, cm: ColorMode) -> TreeNode {
+  let title = text.color_256(8).s('{').s(map.len()).s('}').clear();
+  let children = map.iter().map(|(key, value)| entry(Text::new(cm).s(key), value, cm)).collect();
+  TreeNode::Node(title, children, Color::White, cm)
+}
+
+/// Builds the node of an array, the title is followed by the number of items.
+fn array(text: Text, items: &[Value], cm: ColorMode) -> TreeNode {
+  let title = text.color_256(8).s('[').s(items.len()).s(']').clear();
+  let children = items.iter().enumerate().map(|(i, value)| entry(Text::new(cm).s('[').s(i).s(']'), value, cm)).collect();
+  TreeNode::Node(title, children, Color::White, cm)
+}
+
+/// Appends a scalar value, or an empty object or array, colored by type.
+fn scalar(text: Text, value: &Value) -> Text {
+  match value {
+    Value::String(_) => text.green().s(value).clear(),
+    Value::Number(_) => text.cyan().s(value).clear(),
+    Value::Bool(_) => text.yellow().s(value).clear(),
+    _ => text.color_256(8).s(value).clear(),
+  }
+}
@@ -1,9 +1,33 @@
 //! # Styled text and tree in terminal
 
 mod colors;
+mod export;
+mod fs;
+mod generic;
+mod html;
+#[cfg(feature = "json")]
+mod json;
+mod options;
+mod outline;
+mod paths;
+mod style;
+mod svg;
 mod text;
+mod transform;
+mod traverse;
 mod tree;
+mod writer;
 
 pub use colors::*;
+pub use fs::*;
+pub use generic::*;
+#[cfg(feature = "json")]
+pub use json::*;
+pub use options::*;
+pub use outline::*;
+pub use paths::*;
+pub use style::*;
+pub use svg::*;
 pub use text::*;
+pub use traverse::*;
 pub use tree::*;
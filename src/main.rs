@@ -1,10 +1,4 @@
-mod colors;
-mod text;
-mod tree;
-
-use colors::*;
-use text::*;
-use tree::*;
+use antex::*;
 
 fn foreground_colors_8(cm: ColorMode) {
   println!("\nForeground 8 colors:\n");
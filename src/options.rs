@@ -0,0 +1,203 @@
+//! # Tree rendering options
+
+use crate::colors::ColorMode;
+use crate::text::visible_width;
+
+/// Glyphs used to draw guides of a tree.
+///
+/// All glyphs in a style should have the same visible width.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TreeStyle {
+  /// Glyph drawn in levels with no more child nodes below.
+  pub(crate) none: String,
+  /// Glyph drawn before the last child node.
+  pub(crate) edge: String,
+  /// Glyph drawn in levels with more child nodes below.
+  pub(crate) pipe: String,
+  /// Glyph drawn before a child node that is not the last one.
+  pub(crate) fork: String,
+}
+
+impl Default for TreeStyle {
+  fn default() -> Self {
+    Self::unicode()
+  }
+}
+
+impl TreeStyle {
+  /// Creates a tree style from custom glyphs.
+  pub fn new(none: impl Into<String>, edge: impl Into<String>, pipe: impl Into<String>, fork: impl Into<String>) -> Self {
+    Self {
+      none: none.into(),
+      edge: edge.into(),
+      pipe: pipe.into(),
+      fork: fork.into(),
+    }
+  }
+
+  /// Unicode box-drawing glyphs, the default style: `├─`, `└─`.
+  pub fn unicode() -> Self {
+    Self::new("   ", " └─", " │ ", " ├─")
+  }
+
+  /// Pure ASCII glyphs: `|--`, `` `-- ``.
+  pub fn ascii() -> Self {
+    Self::new("   ", "`--", "|  ", "|--")
+  }
+
+  /// Unicode glyphs with rounded corner: `├─`, `╰─`.
+  pub fn rounded() -> Self {
+    Self::new("   ", " ╰─", " │ ", " ├─")
+  }
+
+  /// Heavy Unicode glyphs: `┣━`, `┗━`.
+  pub fn heavy() -> Self {
+    Self::new("   ", " ┗━", " ┃ ", " ┣━")
+  }
+
+  /// Double-line Unicode glyphs: `╠═`, `╚═`.
+  pub fn double() -> Self {
+    Self::new("   ", " ╚═", " ║ ", " ╠═")
+  }
+
+  /// Returns the visible width of glyphs in this style.
+  pub fn width(&self) -> usize {
+    visible_width(&self.fork)
+  }
+
+  /// Returns the same style with glyphs stretched or compressed to specified width.
+  ///
+  /// Glyphs are stretched by repeating their last character, e.g. ` ├─` becomes ` ├───`,
+  /// and compressed by removing leading spaces first and then trailing characters.
+  pub fn with_width(&self, width: usize) -> Self {
+    Self {
+      none: resize_glyph(&self.none, width),
+      edge: resize_glyph(&self.edge, width),
+      pipe: resize_glyph(&self.pipe, width),
+      fork: resize_glyph(&self.fork, width),
+    }
+  }
+}
+
+/// Stretches or compresses a single glyph to specified width.
+fn resize_glyph(glyph: &str, width: usize) -> String {
+  let mut chars = glyph.chars().collect::<Vec<char>>();
+  let mut glyph_width = visible_width(glyph);
+  while glyph_width > width && chars.len() > 1 && chars[0] == ' ' {
+    chars.remove(0);
+    glyph_width -= 1;
+  }
+  while glyph_width > width {
+    match chars.pop() {
+      Some(ch) => glyph_width = glyph_width.saturating_sub(visible_width(ch.encode_utf8(&mut [0; 4]))),
+      None => break,
+    }
+  }
+  if let Some(&last) = chars.last() {
+    let last_width = visible_width(last.encode_utf8(&mut [0; 4])).max(1);
+    while glyph_width + last_width <= width {
+      chars.push(last);
+      glyph_width += last_width;
+    }
+  }
+  let mut glyph = chars.into_iter().collect::<String>();
+  glyph.push_str(&" ".repeat(width.saturating_sub(glyph_width)));
+  glyph
+}
+
+/// Options controlling how a [TreeNode](crate::TreeNode) is rendered.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+  /// Maximum width of rendered lines, overlong lines are soft-wrapped.
+  pub(crate) max_width: Option<usize>,
+  /// Glyphs used to draw tree guides.
+  pub(crate) tree_style: TreeStyle,
+  /// Width of a single tree level, natural width of glyphs when not set.
+  pub(crate) level_width: Option<usize>,
+  /// Number of spaces between a guide and a label.
+  pub(crate) gap: usize,
+  /// Maximum depth of rendered nodes, deeper levels are collapsed.
+  pub(crate) max_depth: Option<usize>,
+  /// Maximum number of child nodes rendered per node.
+  pub(crate) max_children: Option<usize>,
+  /// Color mode used instead of color modes of nodes and texts.
+  pub(crate) color_mode: Option<ColorMode>,
+}
+
+impl Default for RenderOptions {
+  fn default() -> Self {
+    Self {
+      max_width: None,
+      tree_style: TreeStyle::default(),
+      level_width: None,
+      gap: 1,
+      max_depth: None,
+      max_children: None,
+      color_mode: None,
+    }
+  }
+}
+
+impl RenderOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the maximum width of rendered lines.
+  ///
+  /// Node titles and leaf lines wider than the space left after tree guides
+  /// are soft-wrapped, continuation lines keep the guides of the tree.
+  pub fn max_width(mut self, max_width: usize) -> Self {
+    self.max_width = Some(max_width);
+    self
+  }
+
+  /// Sets the glyphs used to draw tree guides.
+  pub fn tree_style(mut self, tree_style: TreeStyle) -> Self {
+    self.tree_style = tree_style;
+    self
+  }
+
+  /// Sets the width of a single tree level, glyphs are stretched or compressed to match.
+  pub fn level_width(mut self, level_width: usize) -> Self {
+    self.level_width = Some(level_width.max(1));
+    self
+  }
+
+  /// Sets the number of spaces between a guide and a label, one space by default.
+  pub fn gap(mut self, gap: usize) -> Self {
+    self.gap = gap;
+    self
+  }
+
+  /// Sets the maximum depth of rendered nodes, the root node has depth zero.
+  ///
+  /// Child nodes of a node at maximum depth are replaced with a single
+  /// summary line like `[3 levels collapsed]`.
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Sets the maximum number of child nodes rendered per node.
+  ///
+  /// Remaining child nodes are replaced with a single summary line like `… 12 more`.
+  pub fn max_children(mut self, max_children: usize) -> Self {
+    self.max_children = Some(max_children);
+    self
+  }
+
+  /// Sets the color mode used instead of color modes of nodes and texts.
+  pub fn color_mode(mut self, cm: ColorMode) -> Self {
+    self.color_mode = Some(cm);
+    self
+  }
+
+  /// Returns the glyphs used to draw tree guides, resized to the level width.
+  pub(crate) fn glyphs(&self) -> TreeStyle {
+    match self.level_width {
+      Some(width) => self.tree_style.with_width(width),
+      None => self.tree_style.clone(),
+    }
+  }
+}
@@ -0,0 +1,106 @@
+//! # Tree from an indented outline
+
+use crate::colors::{Color, ColorMode};
+use crate::text::{StyledText, Text};
+use crate::tree::TreeNode;
+
+/// Parses indented plain text or nested Markdown lists into trees with default options.
+pub fn outline(input: &str, color: Color, cm: ColorMode) -> Vec<TreeNode> {
+  OutlineBuilder::new(color, cm).build(input)
+}
+
+/// Builder of trees from indentation-based text or nested Markdown bullet lists.
+///
+/// Every non-empty line is an item, a line becomes a child of the nearest preceding
+/// line with smaller indentation. Items with child items become [TreeNode::Node],
+/// the remaining ones become [TreeNode::Leaf]. List markers like `-`, `*`, `+`, `1.`
+/// or `1)` followed by a space are removed from the beginning of items.
+#[derive(Debug, Clone)]
+pub struct OutlineBuilder {
+  /// Color of tree guides.
+  color: Color,
+  /// Color mode.
+  cm: ColorMode,
+  /// Number of columns a tab character counts for in indentation.
+  tab_width: usize,
+}
+
+impl OutlineBuilder {
+  pub fn new(color: Color, cm: ColorMode) -> Self {
+    Self { color, cm, tab_width: 4 }
+  }
+
+  /// Sets the number of columns a tab character counts for in indentation, four by default.
+  pub fn tab_width(mut self, tab_width: usize) -> Self {
+    self.tab_width = tab_width;
+    self
+  }
+
+  /// Builds trees from the outline, one for every item without indentation.
+  pub fn build(&self, input: &str) -> Vec<TreeNode> {
+    let mut roots = vec![];
+    // items still collecting child items, with their indentation
+    let mut open: Vec<(usize, &str, Vec<TreeNode>)> = vec![];
+    for line in input.lines() {
+      let content = line.trim_start();
+      if content.trim_end().is_empty() {
+        continue;
+      }
+      let indent = self.indentation(&line[..line.len() - content.len()]);
+      while open.last().is_some_and(|(open_indent, _, _)| *open_indent >= indent) {
+        self.close(&mut open, &mut roots);
+      }
+      open.push((indent, strip_marker(content.trim_end()), vec![]));
+    }
+    while !open.is_empty() {
+      self.close(&mut open, &mut roots);
+    }
+    roots
+  }
+
+  /// Returns the width of indentation in columns.
+  fn indentation(&self, whitespace: &str) -> usize {
+    whitespace.chars().fold(0, |width, ch| {
+      if ch == '\t' {
+        width - width % self.tab_width.max(1) + self.tab_width
+      } else {
+        width + 1
+      }
+    })
+  }
+
+  /// Closes the innermost open item, appending it to its parent or to roots.
+  fn close(&self, open: &mut Vec<(usize, &str, Vec<TreeNode>)>, roots: &mut Vec<TreeNode>) {
+    if let Some((_, content, children)) = open.pop() {
+      let text = Text::new(self.cm).s(content);
+      let node = if children.is_empty() {
+        TreeNode::Leaf(vec![text])
+      } else {
+        TreeNode::Node(text, children, self.color, self.cm)
+      };
+      match open.last_mut() {
+        Some((_, _, siblings)) => siblings.push(node),
+        None => roots.push(node),
+      }
+    }
+  }
+}
+
+/// Removes the Markdown list marker from the beginning of an item.
+fn strip_marker(content: &str) -> &str {
+  let rest = match content.strip_prefix(['-', '*', '+']) {
+    Some(rest) => rest,
+    None => {
+      let digits = content.len() - content.trim_start_matches(|ch: char| ch.is_ascii_digit()).len();
+      match content[digits..].strip_prefix(['.', ')']) {
+        Some(rest) if digits > 0 => rest,
+        _ => return content,
+      }
+    }
+  };
+  match rest.strip_prefix([' ', '\t']) {
+    Some(item) => item.trim_start(),
+    None if rest.is_empty() => rest,
+    None => content,
+  }
+}
@@ -0,0 +1,120 @@
+//! # Tree from a list of paths
+
+use crate::colors::{Color, ColorMode};
+use crate::text::{StyledText, Text};
+use crate::tree::TreeNode;
+use std::collections::HashMap;
+
+/// Builds trees from paths separated with slashes, see [PathTreeBuilder].
+pub fn path_tree<I, S>(paths: I, color: Color, cm: ColorMode) -> Vec<TreeNode>
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  PathTreeBuilder::new(color, cm).build(paths)
+}
+
+/// Builder of trees from paths like `a/b/c.rs` or `crate::module::item`.
+///
+/// Paths sharing a prefix are merged into a single node, segments are kept
+/// in the order they first appeared. Empty segments are skipped.
+#[derive(Debug, Clone)]
+pub struct PathTreeBuilder {
+  /// Color of tree guides.
+  color: Color,
+  /// Color mode.
+  cm: ColorMode,
+  /// Separator of path segments.
+  separator: String,
+}
+
+/// Segment of merged paths.
+struct Segment {
+  name: String,
+  value: Option<Text>,
+  children: Vec<Segment>,
+  /// Indexes of child segments by name.
+  index: HashMap<String, usize>,
+}
+
+impl Segment {
+  fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      value: None,
+      children: vec![],
+      index: HashMap::new(),
+    }
+  }
+
+  /// Returns the child segment with specified name, inserting it when missing.
+  fn child(&mut self, name: &str) -> &mut Segment {
+    let index = match self.index.get(name) {
+      Some(index) => *index,
+      None => {
+        self.index.insert(name.to_string(), self.children.len());
+        self.children.push(Segment::new(name));
+        self.children.len() - 1
+      }
+    };
+    &mut self.children[index]
+  }
+}
+
+impl PathTreeBuilder {
+  pub fn new(color: Color, cm: ColorMode) -> Self {
+    Self {
+      color,
+      cm,
+      separator: "/".to_string(),
+    }
+  }
+
+  /// Sets the separator of path segments, `/` by default.
+  pub fn separator(mut self, separator: &str) -> Self {
+    self.separator = separator.to_string();
+    self
+  }
+
+  /// Builds trees from paths, one for every distinct first segment.
+  pub fn build<I, S>(&self, paths: I) -> Vec<TreeNode>
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    self.build_with_values(paths.into_iter().map(|path| (path, None)))
+  }
+
+  /// Builds trees from paths with optional values, displayed after the last segment of each path.
+  pub fn build_with_values<I, S>(&self, entries: I) -> Vec<TreeNode>
+  where
+    I: IntoIterator<Item = (S, Option<Text>)>,
+    S: AsRef<str>,
+  {
+    let mut root = Segment::new("");
+    for (path, value) in entries {
+      let mut segment = &mut root;
+      for name in path.as_ref().split(self.separator.as_str()).filter(|name| !name.is_empty()) {
+        segment = segment.child(name);
+      }
+      if value.is_some() {
+        segment.value = value;
+      }
+    }
+    root.children.into_iter().map(|segment| self.node(segment)).collect()
+  }
+
+  /// Converts a merged segment into a tree node.
+  fn node(&self, segment: Segment) -> TreeNode {
+    let mut text = Text::new(self.cm).s(segment.name);
+    if let Some(value) = segment.value {
+      text = text.space() + value;
+    }
+    if segment.children.is_empty() {
+      TreeNode::Leaf(vec![text])
+    } else {
+      let children = segment.children.into_iter().map(|child| self.node(child)).collect();
+      TreeNode::Node(text, children, self.color, self.cm)
+    }
+  }
+}
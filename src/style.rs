@@ -0,0 +1,127 @@
+//! # Text style
+
+use crate::colors::{Color, ColorMode};
+use std::fmt;
+
+/// Style of a piece of text: colors and attributes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Style {
+  /// Foreground color.
+  pub fg: Option<Color>,
+  /// Background color.
+  pub bg: Option<Color>,
+  /// Bold attribute.
+  pub bold: bool,
+  /// Italic attribute.
+  pub italic: bool,
+  /// Underline attribute.
+  pub underline: bool,
+}
+
+impl Style {
+  /// Returns `true` when no color and no attribute is set.
+  pub fn is_plain(&self) -> bool {
+    *self == Self::default()
+  }
+
+  /// Writes escape sequences switching the terminal from style `from` to this style.
+  ///
+  /// When any color or attribute has to be switched off, the style is cleared first
+  /// and all colors and attributes of this style are written again.
+  pub fn write_transition(&self, f: &mut dyn fmt::Write, from: &Style, cm: ColorMode) -> fmt::Result {
+    if self == from || cm == ColorMode::Off {
+      return Ok(());
+    }
+    let reset = (from.fg.is_some() && self.fg.is_none())
+      || (from.bg.is_some() && self.bg.is_none())
+      || (from.bold && !self.bold)
+      || (from.italic && !self.italic)
+      || (from.underline && !self.underline);
+    let base = if reset {
+      f.write_str(cm.clear())?;
+      Style::default()
+    } else {
+      *from
+    };
+    if self.fg != base.fg {
+      if let Some(c) = self.fg {
+        f.write_str(&cm.color(c))?;
+      }
+    }
+    if self.bg != base.bg {
+      if let Some(c) = self.bg {
+        f.write_str(&cm.bg_color(c))?;
+      }
+    }
+    if self.bold && !base.bold {
+      f.write_str(cm.bold())?;
+    }
+    if self.italic && !base.italic {
+      f.write_str(cm.italic())?;
+    }
+    if self.underline && !base.underline {
+      f.write_str(cm.underline())?;
+    }
+    Ok(())
+  }
+
+  /// Applies parameters of an SGR escape sequence, like `1;38;5;208` or `38:2::255:135:0`, to this style.
+  ///
+  /// Unsupported and malformed parameters are ignored, empty parameters reset the style.
+  pub(crate) fn apply_sgr(&mut self, params: &str) {
+    let mut params = params.split(';');
+    while let Some(param) = params.next() {
+      let mut parts = param.split(':');
+      let code = match parts.next().unwrap_or_default() {
+        "" => 0,
+        code => match code.parse::<u16>() {
+          Ok(code) => code,
+          Err(_) => continue,
+        },
+      };
+      let sub_params = parts.collect::<Vec<&str>>();
+      match code {
+        0 => *self = Style::default(),
+        1 => self.bold = true,
+        3 => self.italic = true,
+        // `4:0` switches underline off, other sub-parameters select the underline shape
+        4 => self.underline = sub_params.first() != Some(&"0"),
+        22 => self.bold = false,
+        23 => self.italic = false,
+        24 => self.underline = false,
+        30..=37 => self.fg = Some(Color::from(code as u8 - 30)),
+        38 => self.fg = extended_color(&sub_params, &mut params).or(self.fg),
+        39 => self.fg = None,
+        40..=47 => self.bg = Some(Color::from(code as u8 - 40)),
+        48 => self.bg = extended_color(&sub_params, &mut params).or(self.bg),
+        49 => self.bg = None,
+        90..=97 => self.fg = Some(Color::Long(code as u8 - 82)),
+        100..=107 => self.bg = Some(Color::Long(code as u8 - 92)),
+        _ => {}
+      }
+    }
+  }
+}
+
+/// Reads a 256 color or an RGB color of an SGR escape sequence.
+///
+/// Colors are read from colon separated sub-parameters (`5:n`, `2:r:g:b` or `2:colorspace:r:g:b`) when present,
+/// or from the following parameters (`5;n` or `2;r;g;b`) otherwise.
+fn extended_color<'a>(sub_params: &[&str], params: &mut impl Iterator<Item = &'a str>) -> Option<Color> {
+  let component = |s: &str| s.parse::<u8>().ok();
+  if sub_params.is_empty() {
+    let mut next = || params.next().and_then(component);
+    return match next()? {
+      5 => next().map(Color::Long),
+      2 => Some(Color::Rgb((next()?, next()?, next()?))),
+      _ => None,
+    };
+  }
+  match sub_params {
+    ["5", n] => component(n).map(Color::Long),
+    ["2", r, g, b] | ["2", _, r, g, b, ..] => Some(Color::Rgb((component(r)?, component(g)?, component(b)?))),
+    _ => None,
+  }
+}
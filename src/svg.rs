@@ -0,0 +1,229 @@
+//! # SVG output
+
+use crate::colors::{Color, ColorMode};
+use crate::options::RenderOptions;
+use crate::style::Style;
+use crate::text::{pieces, visible_width, Piece, Span, Text};
+use crate::tree::TreeNode;
+use std::fmt;
+use std::fmt::Write;
+
+/// Theme of SVG images rendering terminal output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgTheme {
+  /// Color of the background.
+  pub(crate) background: Color,
+  /// Color of text without foreground color.
+  pub(crate) foreground: Color,
+  /// Monospace font family.
+  pub(crate) font_family: String,
+  /// Font size in pixels.
+  pub(crate) font_size: f64,
+  /// Space around the text in pixels.
+  pub(crate) padding: f64,
+}
+
+impl Default for SvgTheme {
+  fn default() -> Self {
+    Self::dark()
+  }
+}
+
+impl SvgTheme {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Dark theme, light gray text on almost black background.
+  pub fn dark() -> Self {
+    Self {
+      background: Color::Rgb((30, 30, 30)),
+      foreground: Color::Long(7),
+      font_family: "monospace".to_string(),
+      font_size: 14.0,
+      padding: 10.0,
+    }
+  }
+
+  /// Light theme, black text on white background.
+  pub fn light() -> Self {
+    Self {
+      background: Color::Rgb((255, 255, 255)),
+      foreground: Color::Long(0),
+      ..Self::dark()
+    }
+  }
+
+  /// Sets the color of the background.
+  pub fn background(mut self, color: Color) -> Self {
+    self.background = color;
+    self
+  }
+
+  /// Sets the color of text without foreground color.
+  pub fn foreground(mut self, color: Color) -> Self {
+    self.foreground = color;
+    self
+  }
+
+  /// Sets the monospace font family, like `'DejaVu Sans Mono', monospace`.
+  pub fn font_family(mut self, font_family: &str) -> Self {
+    self.font_family = font_family.to_string();
+    self
+  }
+
+  /// Sets the font size in pixels, the grid cell is 0.6 of the font size wide and 1.2 high.
+  pub fn font_size(mut self, font_size: f64) -> Self {
+    self.font_size = font_size;
+    self
+  }
+
+  /// Sets the space around the text in pixels.
+  pub fn padding(mut self, padding: f64) -> Self {
+    self.padding = padding;
+    self
+  }
+}
+
+impl Text {
+  /// Returns the text as an SVG image, like displayed in a terminal with specified theme.
+  ///
+  /// Styles are kept whatever the color mode of the text is.
+  pub fn to_svg(&self, theme: &SvgTheme) -> String {
+    let mut writer = SvgWriter::default();
+    let _ = self.write(&mut writer, ColorMode::On);
+    writer.finish(theme)
+  }
+}
+
+impl TreeNode {
+  /// Returns the tree as an SVG image, like displayed in a terminal with specified theme.
+  pub fn to_svg(&self, theme: &SvgTheme) -> String {
+    self.to_svg_with(theme, &RenderOptions::default())
+  }
+
+  /// Returns the tree as an SVG image using specified theme and rendering options.
+  pub fn to_svg_with(&self, theme: &SvgTheme, options: &RenderOptions) -> String {
+    let mut writer = SvgWriter::default();
+    let _ = self.write_with(&mut writer, &options.clone().color_mode(ColorMode::On));
+    writer.finish(theme)
+  }
+}
+
+/// Adapter collecting lines of styled spans from text with escape sequences.
+#[derive(Default)]
+struct SvgWriter {
+  /// Collected lines, the last one is being filled.
+  lines: Vec<Vec<Span>>,
+  /// Style set by escape sequences written so far.
+  style: Style,
+}
+
+impl fmt::Write for SvgWriter {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for piece in pieces(s) {
+      match piece {
+        Piece::Text(text) => {
+          for (i, part) in text.split('\n').enumerate() {
+            if i > 0 || self.lines.is_empty() {
+              self.lines.push(vec![]);
+            }
+            if !part.is_empty() {
+              self.lines.last_mut().unwrap().push(Span::new(self.style, part));
+            }
+          }
+        }
+        Piece::Sgr(params) => self.style.apply_sgr(params),
+      }
+    }
+    Ok(())
+  }
+}
+
+impl SvgWriter {
+  /// Returns the SVG image displaying collected lines on a monospace grid.
+  fn finish(mut self, theme: &SvgTheme) -> String {
+    if self.lines.last().is_some_and(|line| line.is_empty()) {
+      self.lines.pop();
+    }
+    let cell_width = theme.font_size * 0.6;
+    let line_height = theme.font_size * 1.2;
+    let columns = self.lines.iter().map(|line| line.iter().map(|span| visible_width(&span.content)).sum()).max().unwrap_or(0);
+    let width = columns as f64 * cell_width + 2.0 * theme.padding;
+    let height = self.lines.len() as f64 * line_height + 2.0 * theme.padding;
+    let mut svg = String::new();
+    let _ = writeln!(
+      svg,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{}" font-size="{}">"#,
+      escape(&theme.font_family),
+      px(theme.font_size),
+      w = px(width),
+      h = px(height)
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, theme.background.to_hex());
+    for (row, line) in self.lines.iter().enumerate() {
+      let top = theme.padding + row as f64 * line_height;
+      // baseline placed to center the text vertically in the line
+      let baseline = top + (line_height + theme.font_size * 0.7) / 2.0;
+      let mut texts = String::new();
+      let mut column = 0;
+      for span in line {
+        let x = theme.padding + column as f64 * cell_width;
+        let span_width = visible_width(&span.content);
+        if let Some(bg) = span.style.bg {
+          let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            px(x),
+            px(top),
+            px(span_width as f64 * cell_width),
+            px(line_height),
+            bg.to_hex()
+          );
+        }
+        if !span.content.trim().is_empty() {
+          let _ = write!(
+            texts,
+            r#"<tspan x="{}" fill="{}"{}>{}</tspan>"#,
+            px(x),
+            span.style.fg.unwrap_or(theme.foreground).to_hex(),
+            attributes(&span.style),
+            escape(&span.content)
+          );
+        }
+        column += span_width;
+      }
+      if !texts.is_empty() {
+        let _ = writeln!(svg, r#"<text y="{}" xml:space="preserve">{}</text>"#, px(baseline), texts);
+      }
+    }
+    svg.push_str("</svg>\n");
+    svg
+  }
+}
+
+/// Returns SVG presentation attributes of text attributes of a style.
+fn attributes(style: &Style) -> String {
+  let mut attributes = String::new();
+  if style.bold {
+    attributes.push_str(r#" font-weight="bold""#);
+  }
+  if style.italic {
+    attributes.push_str(r#" font-style="italic""#);
+  }
+  if style.underline {
+    attributes.push_str(r#" text-decoration="underline""#);
+  }
+  attributes
+}
+
+/// Formats a length in pixels, without trailing zeros.
+fn px(value: f64) -> String {
+  let formatted = format!("{:.2}", value);
+  formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Escapes characters with special meaning in XML.
+fn escape(s: &str) -> String {
+  s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
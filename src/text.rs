@@ -1,7 +1,10 @@
 use crate::colors::{Color, ColorMode, RgbColor};
+use crate::style::Style;
 use std::fmt;
 use std::fmt::{Display, Write};
 use std::ops::Add;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub trait StyledText {
   fn s<T: Display>(self, s: T) -> Self;
@@ -42,17 +45,101 @@ pub trait StyledText {
   fn italic(self) -> Self;
   fn underline(self) -> Self;
   fn clear(self) -> Self;
+  /// Pads the text with spaces on the left to specified visible width.
+  fn pad_left(self, width: usize) -> Self
+  where
+    Self: Sized,
+  {
+    self.pad_left_with(width, ' ')
+  }
+  /// Pads the text with spaces on the right to specified visible width.
+  fn pad_right(self, width: usize) -> Self
+  where
+    Self: Sized,
+  {
+    self.pad_right_with(width, ' ')
+  }
+  /// Pads the text with spaces on both sides to specified visible width.
+  fn center(self, width: usize) -> Self
+  where
+    Self: Sized,
+  {
+    self.center_with(width, ' ')
+  }
+  /// Pads the text with fill characters on the left to specified visible width.
+  fn pad_left_with(self, width: usize, fill: char) -> Self;
+  /// Pads the text with fill characters on the right to specified visible width.
+  fn pad_right_with(self, width: usize, fill: char) -> Self;
+  /// Pads the text with fill characters on both sides to specified visible width.
+  fn center_with(self, width: usize, fill: char) -> Self;
 }
 
+/// Piece of text sharing a single style.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+  /// Style of the content.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub style: Style,
+  /// Plain content, without any escape sequences.
+  pub content: String,
+}
+
+impl Span {
+  pub fn new(style: Style, content: impl Into<String>) -> Self {
+    Self { style, content: content.into() }
+  }
+}
+
+/// Styled text built from a list of spans.
+///
+/// Escape sequences are generated only when the text is written,
+/// so the same text can be rendered in any [ColorMode].
+///
+/// With `serde` feature the text is serialized as a list of spans,
+/// and can be deserialized either from spans or from plain content,
+/// text deserialized from plain content has no styles, so its color mode is [ColorMode::Off].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "TextRepr"))]
 pub struct Text {
+  /// Color mode used when displaying the text.
   color_mode: ColorMode,
-  content: String,
+  /// Styled pieces of the text.
+  spans: Vec<Span>,
+  /// Style applied to the content appended next.
+  style: Style,
+}
+
+/// Serialized forms of [Text].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum TextRepr {
+  /// Plain content without styles, deserialized with [ColorMode::Off].
+  Plain(String),
+  /// Styled spans.
+  Spans {
+    color_mode: ColorMode,
+    spans: Vec<Span>,
+    #[serde(default)]
+    style: Style,
+  },
+}
+
+#[cfg(feature = "serde")]
+impl From<TextRepr> for Text {
+  fn from(repr: TextRepr) -> Self {
+    match repr {
+      TextRepr::Plain(content) => Text::new(ColorMode::Off).s(content),
+      TextRepr::Spans { color_mode, spans, style } => Self { color_mode, spans, style },
+    }
+  }
 }
 
 impl Display for Text {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "{}", self.content)
+    self.write(f, self.color_mode)
   }
 }
 
@@ -72,30 +159,242 @@ impl Text {
   pub fn new(color_mode: ColorMode) -> Self {
     Self {
       color_mode,
-      content: String::default(),
+      spans: Vec::default(),
+      style: Style::default(),
+    }
+  }
+
+  /// Creates a text from a string colored with SGR escape sequences, like output of other tools.
+  ///
+  /// Colors and attributes become styles of spans, other escape sequences are dropped.
+  /// The style set at the end of the string applies to content appended next.
+  /// The text is displayed with [ColorMode::Off] whatever the output is, use [Text::with_color_mode] to display its styles.
+  pub fn from_ansi(s: &str) -> Self {
+    let mut text = Self::new(ColorMode::Off);
+    for piece in pieces(s) {
+      match piece {
+        Piece::Text(content) => text.push(Span::new(text.style, content)),
+        Piece::Sgr(params) => text.style.apply_sgr(params),
+      }
+    }
+    text
+  }
+
+  /// Returns the content of the text without any escape sequences, whatever the color mode is.
+  ///
+  /// Escape sequences appended as content, like hyperlinks or colors of other tools, are removed too.
+  pub fn plain(&self) -> String {
+    self.spans.iter().map(|span| strip_escapes(&span.content)).collect()
+  }
+
+  /// Returns the color mode used when displaying the text.
+  pub fn color_mode(&self) -> ColorMode {
+    self.color_mode
+  }
+
+  /// Sets the color mode used when displaying the text.
+  pub fn set_color_mode(&mut self, cm: ColorMode) {
+    self.color_mode = cm;
+  }
+
+  /// Returns the same text displayed in another color mode.
+  pub fn with_color_mode(mut self, cm: ColorMode) -> Self {
+    self.color_mode = cm;
+    self
+  }
+
+  /// Returns styled pieces of the text.
+  pub fn spans(&self) -> &[Span] {
+    &self.spans
+  }
+
+  /// Returns the style applied to the content appended next.
+  pub fn style(&self) -> Style {
+    self.style
+  }
+
+  /// Appends a span, the current style is not changed.
+  pub fn push(&mut self, span: Span) {
+    if span.content.is_empty() {
+      return;
+    }
+    match self.spans.last_mut() {
+      Some(last) if last.style == span.style => last.content.push_str(&span.content),
+      _ => self.spans.push(span),
+    }
+  }
+
+  /// Returns the number of terminal columns taken by the widest line of the text.
+  ///
+  /// Escape sequences are skipped, East Asian wide characters and emoji take two columns.
+  pub fn width(&self) -> usize {
+    let mut max_width = 0;
+    let mut width = 0;
+    for span in &self.spans {
+      let mut lines = span.content.split('\n');
+      if let Some(line) = lines.next() {
+        width += line_width(line);
+      }
+      for line in lines {
+        max_width = max_width.max(width);
+        width = line_width(line);
+      }
+    }
+    max_width.max(width)
+  }
+
+  /// Returns the text cut to specified visible width.
+  ///
+  /// Each line wider than `max_width` is cut on a grapheme cluster boundary
+  /// and ended with `ellipsis` in the style active at the cut. Escape sequences
+  /// in the content are never split, those after the cut are dropped and a reset
+  /// follows the ellipsis when any was kept. The style of the truncated text is
+  /// cleared at the end, so colors do not bleed past the cut.
+  pub fn truncate(&self, max_width: usize, ellipsis: &str) -> Text {
+    if self.width() <= max_width {
+      return self.clone();
+    }
+    let limit = max_width.saturating_sub(visible_width(ellipsis));
+    let ellipsis = if visible_width(ellipsis) <= max_width { ellipsis } else { "" };
+    let plain = self.spans.iter().map(|span| span.content.as_str()).collect::<String>();
+    let line_widths = plain.split('\n').map(line_width).collect::<Vec<usize>>();
+    let mut text = Text::new(self.color_mode);
+    let mut line = 0;
+    let mut width = 0;
+    let mut cut = false;
+    // flag indicating if an escape sequence from the content was kept
+    let mut escaped = false;
+    for span in &self.spans {
+      let mut content = String::new();
+      for token in tokens(&span.content) {
+        if token.ends_with('\n') {
+          content.push_str(token);
+          line += 1;
+          width = 0;
+          cut = false;
+        } else if token.starts_with('\u{1b}') {
+          if !cut {
+            content.push_str(token);
+            escaped = true;
+          }
+        } else if line_widths[line] <= max_width {
+          content.push_str(token);
+        } else if !cut {
+          let token_width = token.width();
+          if width + token_width <= limit {
+            content.push_str(token);
+            width += token_width;
+          } else {
+            content.push_str(ellipsis);
+            if escaped {
+              content.push_str("\u{1b}[0m");
+            }
+            cut = true;
+          }
+        }
+      }
+      text.push(Span::new(span.style, content));
+    }
+    text
+  }
+
+  /// Returns the text broken into lines not wider than specified visible width.
+  ///
+  /// Lines are broken on word boundaries, words wider than `width` are split
+  /// on grapheme cluster boundaries. Each line keeps the styles of its spans,
+  /// so continuation lines open with the style that was active at the break.
+  pub fn wrap(&self, width: usize) -> Vec<Text> {
+    let mut wrapper = Wrapper::new(self.color_mode, width.max(1));
+    for span in &self.spans {
+      for token in tokens(&span.content) {
+        if token.ends_with('\n') {
+          wrapper.end_line();
+        } else if token.starts_with('\u{1b}') {
+          wrapper.add_word_token(span.style, token, 0);
+        } else if token.chars().all(char::is_whitespace) {
+          wrapper.add_space_token(span.style, token, token.width());
+        } else {
+          wrapper.add_word_token(span.style, token, token.width());
+        }
+      }
+    }
+    let mut lines = wrapper.end();
+    if let Some(last) = lines.last_mut() {
+      last.style = self.style;
     }
+    lines
+  }
+
+  /// Writes the text with escape sequences for specified color mode.
+  pub fn write(&self, f: &mut dyn fmt::Write, cm: ColorMode) -> fmt::Result {
+    let mut current = Style::default();
+    for span in &self.spans {
+      span.style.write_transition(f, &current, cm)?;
+      f.write_str(&span.content)?;
+      current = span.style;
+    }
+    self.style.write_transition(f, &current, cm)
   }
 
   pub fn print(&self) {
-    print!("{}", self.content);
+    print!("{}", self);
   }
 
   pub fn cprint(&self) {
-    print!("{}{}", self.content, self.color_mode.clear());
+    print!("{}{}", self, self.color_mode.clear());
   }
 
   pub fn println(&self) {
-    println!("{}", self.content);
+    println!("{}", self);
   }
 
   pub fn cprintln(&self) {
-    println!("{}{}", self.content, self.color_mode.clear());
+    println!("{}{}", self, self.color_mode.clear());
+  }
+
+  /// Returns the number of fill characters needed to pad the text to specified width.
+  fn fill_count(&self, width: usize, fill: char) -> usize {
+    width.saturating_sub(self.width()) / fill.width().unwrap_or(1).max(1)
+  }
+
+  /// Inserts unstyled fill characters at the beginning of the text.
+  fn prepend_fill(&mut self, fill: char, n: usize) {
+    if n > 0 {
+      let content = fill.to_string().repeat(n);
+      match self.spans.first_mut() {
+        Some(first) if first.style.is_plain() => first.content.insert_str(0, &content),
+        _ => self.spans.insert(0, Span::new(Style::default(), content)),
+      }
+    }
+  }
+
+  /// Appends unstyled fill characters at the end of the text.
+  fn append_fill(&mut self, fill: char, n: usize) {
+    if n > 0 {
+      self.push(Span::new(Style::default(), fill.to_string().repeat(n)));
+    }
+  }
+
+  /// Changes the style applied to the content appended next.
+  fn styled(mut self, f: impl FnOnce(&mut Style)) -> Self {
+    f(&mut self.style);
+    self
   }
 }
 
 impl StyledText for Text {
   fn s<T: Display>(mut self, s: T) -> Self {
-    let _ = write!(&mut self.content, "{}", s);
+    match self.spans.last_mut() {
+      Some(span) if span.style == self.style => {
+        let _ = write!(&mut span.content, "{}", s);
+      }
+      _ => {
+        let content = s.to_string();
+        if !content.is_empty() {
+          self.spans.push(Span::new(self.style, content));
+        }
+      }
+    }
     self
   }
 
@@ -131,152 +430,142 @@ impl StyledText for Text {
     self.s('%')
   }
 
-  fn plural<T: Display>(mut self, s: T, n: usize) -> Self {
-    let _ = if n == 1 {
-      write!(&mut self.content, "{}", s)
+  fn plural<T: Display>(self, s: T, n: usize) -> Self {
+    if n == 1 {
+      self.s(s)
     } else {
-      write!(&mut self.content, "{}s", s)
-    };
-    self
+      self.s(format_args!("{}s", s))
+    }
   }
 
-  fn black(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.black());
-    self
+  fn black(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Black))
   }
 
-  fn red(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.red());
-    self
+  fn red(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Red))
   }
 
-  fn green(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.green());
-    self
+  fn green(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Green))
   }
 
-  fn yellow(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.yellow());
-    self
+  fn yellow(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Yellow))
   }
 
-  fn blue(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.blue());
-    self
+  fn blue(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Blue))
   }
 
-  fn magenta(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.magenta());
-    self
+  fn magenta(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Magenta))
   }
 
-  fn cyan(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.cyan());
-    self
+  fn cyan(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::Cyan))
   }
 
-  fn white(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.white());
-    self
+  fn white(self) -> Self {
+    self.styled(|st| st.fg = Some(Color::White))
   }
 
-  fn bg_black(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_black());
-    self
+  fn bg_black(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Black))
   }
 
-  fn bg_red(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_red());
-    self
+  fn bg_red(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Red))
   }
 
-  fn bg_green(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_green());
-    self
+  fn bg_green(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Green))
   }
 
-  fn bg_yellow(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_yellow());
-    self
+  fn bg_yellow(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Yellow))
   }
 
-  fn bg_blue(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_blue());
-    self
+  fn bg_blue(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Blue))
   }
 
-  fn bg_magenta(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_magenta());
-    self
+  fn bg_magenta(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Magenta))
   }
 
-  fn bg_cyan(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_cyan());
-    self
+  fn bg_cyan(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::Cyan))
   }
 
-  fn bg_white(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_white());
-    self
+  fn bg_white(self) -> Self {
+    self.styled(|st| st.bg = Some(Color::White))
   }
 
-  fn color(mut self, c: Color) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.color(c));
-    self
+  fn color(self, c: Color) -> Self {
+    self.styled(|st| st.fg = Some(c))
   }
 
-  fn bg_color(mut self, c: Color) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_color(c));
-    self
+  fn bg_color(self, c: Color) -> Self {
+    self.styled(|st| st.bg = Some(c))
   }
 
-  fn color_8(mut self, c: u8) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.color_8(c));
-    self
+  fn color_8(self, c: u8) -> Self {
+    self.color(Color::from(c.clamp(0, 7)))
   }
 
-  fn bg_color_8(mut self, c: u8) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_color_8(c));
-    self
+  fn bg_color_8(self, c: u8) -> Self {
+    self.bg_color(Color::from(c.clamp(0, 7)))
   }
 
-  fn color_256(mut self, c: u8) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.color_256(c));
-    self
+  fn color_256(self, c: u8) -> Self {
+    self.color(Color::Long(c))
   }
 
-  fn bg_color_256(mut self, c: u8) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_color_256(c));
-    self
+  fn bg_color_256(self, c: u8) -> Self {
+    self.bg_color(Color::Long(c))
   }
 
-  fn color_rgb(mut self, c: RgbColor) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.color_rgb(c));
-    self
+  fn color_rgb(self, c: RgbColor) -> Self {
+    self.color(Color::Rgb(c))
   }
 
-  fn bg_color_rgb(mut self, c: RgbColor) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bg_color_rgb(c));
-    self
+  fn bg_color_rgb(self, c: RgbColor) -> Self {
+    self.bg_color(Color::Rgb(c))
   }
 
-  fn bold(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.bold());
-    self
+  fn bold(self) -> Self {
+    self.styled(|st| st.bold = true)
+  }
+
+  fn italic(self) -> Self {
+    self.styled(|st| st.italic = true)
   }
 
-  fn italic(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.italic());
+  fn underline(self) -> Self {
+    self.styled(|st| st.underline = true)
+  }
+
+  fn clear(self) -> Self {
+    self.styled(|st| *st = Style::default())
+  }
+
+  fn pad_left_with(mut self, width: usize, fill: char) -> Self {
+    let n = self.fill_count(width, fill);
+    self.prepend_fill(fill, n);
     self
   }
 
-  fn underline(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.underline());
+  fn pad_right_with(mut self, width: usize, fill: char) -> Self {
+    let n = self.fill_count(width, fill);
+    self.append_fill(fill, n);
     self
   }
 
-  fn clear(mut self) -> Self {
-    let _ = write!(&mut self.content, "{}", self.color_mode.clear());
+  fn center_with(mut self, width: usize, fill: char) -> Self {
+    let n = self.fill_count(width, fill);
+    self.prepend_fill(fill, n / 2);
+    self.append_fill(fill, n - n / 2);
     self
   }
 }
@@ -284,12 +573,207 @@ impl StyledText for Text {
 impl Add for Text {
   type Output = Self;
 
-  fn add(self, rhs: Self) -> Self::Output {
-    let mut content = self.content;
-    content.push_str(&rhs.content);
+  fn add(mut self, rhs: Self) -> Self::Output {
+    for span in rhs.spans {
+      self.push(span);
+    }
+    self.style = rhs.style;
+    self
+  }
+}
+
+/// Returns the number of terminal columns taken by the widest line of the string.
+///
+/// Escape sequences are skipped, East Asian wide characters and emoji take two columns.
+pub fn visible_width(s: &str) -> usize {
+  s.split('\n').map(line_width).max().unwrap_or(0)
+}
+
+/// Returns the number of terminal columns taken by a single line.
+fn line_width(line: &str) -> usize {
+  let mut rest = line.strip_suffix('\r').unwrap_or(line);
+  let mut width = 0;
+  while let Some(pos) = rest.find('\u{1b}') {
+    width += rest[..pos].width();
+    rest = &rest[pos + escape_len(&rest[pos..])..];
+  }
+  width + rest.width()
+}
+
+/// Returns the length in bytes of the escape sequence at the beginning of the string.
+pub(crate) fn escape_len(s: &str) -> usize {
+  let bytes = s.as_bytes();
+  match bytes.get(1) {
+    // control sequence, terminated by a byte in range `@`..`~`
+    Some(b'[') => bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)).map_or(bytes.len(), |pos| pos + 3),
+    // operating system command, terminated by `BEL` or `ESC \`
+    Some(b']') => {
+      let mut pos = 2;
+      while pos < bytes.len() {
+        match bytes[pos] {
+          0x07 => return pos + 1,
+          0x1b if bytes.get(pos + 1) == Some(&b'\\') => return pos + 2,
+          _ => pos += 1,
+        }
+      }
+      bytes.len()
+    }
+    Some(_) => 1 + s[1..].chars().next().map_or(0, char::len_utf8),
+    None => 1,
+  }
+}
+
+/// Piece of a string containing escape sequences.
+pub(crate) enum Piece<'a> {
+  /// Visible text without escape sequences.
+  Text(&'a str),
+  /// Parameters of an SGR escape sequence, like `1;31`.
+  Sgr(&'a str),
+}
+
+/// Splits the string into visible text and SGR escape sequences, other escape sequences are dropped.
+pub(crate) fn pieces(s: &str) -> impl Iterator<Item = Piece<'_>> {
+  let mut rest = s;
+  std::iter::from_fn(move || loop {
+    match rest.find('\u{1b}') {
+      _ if rest.is_empty() => return None,
+      Some(0) => {
+        let (sequence, tail) = rest.split_at(escape_len(rest));
+        rest = tail;
+        if let Some(params) = sequence.strip_prefix("\u{1b}[").and_then(|sequence| sequence.strip_suffix('m')) {
+          return Some(Piece::Sgr(params));
+        }
+      }
+      pos => {
+        let (text, tail) = rest.split_at(pos.unwrap_or(rest.len()));
+        rest = tail;
+        return Some(Piece::Text(text));
+      }
+    }
+  })
+}
+
+/// Returns the string with all escape sequences removed.
+pub(crate) fn strip_escapes(s: &str) -> String {
+  let mut stripped = String::new();
+  let mut rest = s;
+  while let Some(pos) = rest.find('\u{1b}') {
+    stripped.push_str(&rest[..pos]);
+    rest = &rest[pos + escape_len(&rest[pos..])..];
+  }
+  stripped.push_str(rest);
+  stripped
+}
+
+/// Splits the string into escape sequences and grapheme clusters.
+fn tokens(s: &str) -> Vec<&str> {
+  let mut tokens = vec![];
+  let mut rest = s;
+  while let Some(pos) = rest.find('\u{1b}') {
+    tokens.extend(rest[..pos].graphemes(true));
+    let len = escape_len(&rest[pos..]);
+    tokens.push(&rest[pos..pos + len]);
+    rest = &rest[pos + len..];
+  }
+  tokens.extend(rest.graphemes(true));
+  tokens
+}
+
+/// Greedy line breaker used by [Text::wrap].
+struct Wrapper {
+  /// Maximum width of a single line.
+  width: usize,
+  /// Lines already wrapped.
+  lines: Vec<Text>,
+  /// Line being filled.
+  line: Text,
+  /// Width of the line being filled.
+  line_width: usize,
+  /// Flag indicating if the line being filled starts after a soft break.
+  wrapped: bool,
+  /// Whitespace waiting before the next word.
+  space: Vec<Span>,
+  /// Width of the whitespace waiting before the next word.
+  space_width: usize,
+  /// Word being collected.
+  word: Vec<Span>,
+  /// Width of the word being collected.
+  word_width: usize,
+}
+
+impl Wrapper {
+  fn new(cm: ColorMode, width: usize) -> Self {
     Self {
-      color_mode: self.color_mode,
-      content,
+      width,
+      lines: vec![],
+      line: Text::new(cm),
+      line_width: 0,
+      wrapped: false,
+      space: vec![],
+      space_width: 0,
+      word: vec![],
+      word_width: 0,
     }
   }
+
+  fn add_space_token(&mut self, style: Style, token: &str, width: usize) {
+    self.place_word();
+    Self::push_token(&mut self.space, style, token);
+    self.space_width += width;
+  }
+
+  fn add_word_token(&mut self, style: Style, token: &str, width: usize) {
+    if self.word_width > 0 && self.word_width + width > self.width {
+      self.place_word();
+    }
+    Self::push_token(&mut self.word, style, token);
+    self.word_width += width;
+  }
+
+  fn push_token(spans: &mut Vec<Span>, style: Style, token: &str) {
+    match spans.last_mut() {
+      Some(span) if span.style == style => span.content.push_str(token),
+      _ => spans.push(Span::new(style, token)),
+    }
+  }
+
+  /// Moves the collected word with preceding whitespace into the line, breaking the line when needed.
+  fn place_word(&mut self) {
+    if self.word.is_empty() {
+      return;
+    }
+    if self.line_width > 0 && self.line_width + self.space_width + self.word_width > self.width {
+      self.break_line(true);
+    }
+    if (self.line_width == 0 && self.wrapped) || self.line_width + self.space_width + self.word_width > self.width {
+      self.space.clear();
+      self.space_width = 0;
+    }
+    for span in self.space.drain(..).chain(self.word.drain(..)) {
+      self.line.push(span);
+    }
+    self.line_width += self.space_width + self.word_width;
+    self.space_width = 0;
+    self.word_width = 0;
+  }
+
+  fn break_line(&mut self, wrapped: bool) {
+    let cm = self.line.color_mode;
+    self.lines.push(std::mem::replace(&mut self.line, Text::new(cm)));
+    self.line_width = 0;
+    self.wrapped = wrapped;
+  }
+
+  /// Ends the line on explicit line break.
+  fn end_line(&mut self) {
+    self.place_word();
+    self.space.clear();
+    self.space_width = 0;
+    self.break_line(false);
+  }
+
+  fn end(mut self) -> Vec<Text> {
+    self.end_line();
+    self.lines
+  }
 }
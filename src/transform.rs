@@ -0,0 +1,75 @@
+//! # Sorting and filtering trees
+
+use crate::text::Text;
+use crate::tree::TreeNode;
+use std::cmp::Ordering;
+
+impl TreeNode {
+  /// Returns the plain text of the title of a node, or of the lines of a leaf joined with newlines.
+  pub fn label(&self) -> String {
+    match self {
+      TreeNode::Node(title, _, _, _) => title.plain(),
+      TreeNode::Leaf(lines) => lines.iter().map(Text::plain).collect::<Vec<String>>().join("\n"),
+    }
+  }
+
+  /// Sorts child nodes of all nodes in the tree with a comparator function.
+  ///
+  /// The sort is stable, nodes comparing equal keep their order.
+  pub fn sort_by<F>(&mut self, mut compare: F)
+  where
+    F: FnMut(&TreeNode, &TreeNode) -> Ordering,
+  {
+    let mut stack = vec![self];
+    while let Some(node) = stack.pop() {
+      if let TreeNode::Node(_, children, _, _) = node {
+        children.sort_by(&mut compare);
+        stack.extend(children.iter_mut());
+      }
+    }
+  }
+
+  /// Sorts child nodes of all nodes in the tree with a key extraction function.
+  pub fn sort_by_key<K, F>(&mut self, mut f: F)
+  where
+    K: Ord,
+    F: FnMut(&TreeNode) -> K,
+  {
+    self.sort_by(|a, b| f(a).cmp(&f(b)));
+  }
+
+  /// Returns a copy of the tree with only nodes matching the predicate and their ancestors.
+  ///
+  /// Ancestors are kept even if they do not match, so matching nodes keep their context.
+  /// Returns `None` when no node in the tree matches.
+  pub fn filter<F>(&self, mut predicate: F) -> Option<TreeNode>
+  where
+    F: FnMut(&TreeNode) -> bool,
+  {
+    // nodes on the path from the root, with the index of the next child node and child nodes kept so far
+    let mut stack: Vec<(&TreeNode, usize, Vec<TreeNode>)> = vec![(self, 0, vec![])];
+    let mut result = None;
+    while let Some((node, next, _)) = stack.last_mut() {
+      let node: &TreeNode = node;
+      if let Some(child) = node.children().get(*next) {
+        *next += 1;
+        stack.push((child, 0, vec![]));
+        continue;
+      }
+      let Some((node, _, kept)) = stack.pop() else { break };
+      let filtered = if predicate(node) || !kept.is_empty() {
+        Some(match node {
+          TreeNode::Node(title, _, color, cm) => TreeNode::Node(title.clone(), kept, *color, *cm),
+          TreeNode::Leaf(lines) => TreeNode::Leaf(lines.clone()),
+        })
+      } else {
+        None
+      };
+      match stack.last_mut() {
+        Some((_, _, parent_kept)) => parent_kept.extend(filtered),
+        None => result = filtered,
+      }
+    }
+    result
+  }
+}
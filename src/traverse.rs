@@ -0,0 +1,155 @@
+//! # Tree traversal
+
+use crate::tree::TreeNode;
+use std::collections::VecDeque;
+
+/// Node visited during traversal, with its position in the tree.
+#[derive(Debug, Clone)]
+pub struct Visit<'a> {
+  /// Visited node.
+  pub node: &'a TreeNode,
+  /// Depth of the node, the root node has depth zero.
+  pub depth: usize,
+  /// Indexes of child nodes leading from the root node to the visited node.
+  pub path: Vec<usize>,
+}
+
+/// Visitor notified when entering and leaving nodes of a tree.
+///
+/// Nodes are entered in pre-order and left in post-order,
+/// see [TreeNode::walk].
+pub trait TreeVisitor {
+  /// Called before any child node of `node` is visited.
+  fn enter(&mut self, _node: &TreeNode, _depth: usize, _path: &[usize]) {}
+  /// Called after all child nodes of `node` were visited.
+  fn leave(&mut self, _node: &TreeNode, _depth: usize, _path: &[usize]) {}
+}
+
+/// Iterator visiting nodes in pre-order: parent first, then child nodes.
+pub struct PreOrder<'a> {
+  stack: Vec<(&'a TreeNode, Vec<usize>)>,
+}
+
+impl<'a> Iterator for PreOrder<'a> {
+  type Item = Visit<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (node, path) = self.stack.pop()?;
+    for (index, child) in node.children().iter().enumerate().rev() {
+      let mut child_path = path.clone();
+      child_path.push(index);
+      self.stack.push((child, child_path));
+    }
+    Some(Visit { node, depth: path.len(), path })
+  }
+}
+
+/// Iterator visiting nodes in post-order: child nodes first, then parent.
+pub struct PostOrder<'a> {
+  /// Nodes on the path from the root, with the index of the next child node to visit.
+  stack: Vec<(&'a TreeNode, usize)>,
+  /// Indexes of child nodes leading to the node on top of the stack.
+  path: Vec<usize>,
+}
+
+impl<'a> Iterator for PostOrder<'a> {
+  type Item = Visit<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let (node, next) = self.stack.last_mut()?;
+      let node: &'a TreeNode = node;
+      match node.children().get(*next) {
+        Some(child) => {
+          self.path.push(*next);
+          *next += 1;
+          self.stack.push((child, 0));
+        }
+        None => {
+          self.stack.pop();
+          let visit = Visit {
+            node,
+            depth: self.path.len(),
+            path: self.path.clone(),
+          };
+          self.path.pop();
+          return Some(visit);
+        }
+      }
+    }
+  }
+}
+
+/// Iterator visiting nodes level by level, from the root node down.
+pub struct BreadthFirst<'a> {
+  queue: VecDeque<(&'a TreeNode, Vec<usize>)>,
+}
+
+impl<'a> Iterator for BreadthFirst<'a> {
+  type Item = Visit<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (node, path) = self.queue.pop_front()?;
+    for (index, child) in node.children().iter().enumerate() {
+      let mut child_path = path.clone();
+      child_path.push(index);
+      self.queue.push_back((child, child_path));
+    }
+    Some(Visit { node, depth: path.len(), path })
+  }
+}
+
+impl TreeNode {
+  /// Returns an iterator visiting all nodes in pre-order.
+  pub fn pre_order(&self) -> PreOrder<'_> {
+    PreOrder { stack: vec![(self, vec![])] }
+  }
+
+  /// Returns an iterator visiting all nodes in post-order.
+  pub fn post_order(&self) -> PostOrder<'_> {
+    PostOrder {
+      stack: vec![(self, 0)],
+      path: vec![],
+    }
+  }
+
+  /// Returns an iterator visiting all nodes in breadth-first order.
+  pub fn breadth_first(&self) -> BreadthFirst<'_> {
+    BreadthFirst {
+      queue: VecDeque::from([(self, vec![])]),
+    }
+  }
+
+  /// Walks the tree depth-first, notifying the visitor when entering and leaving each node.
+  pub fn walk<V: TreeVisitor + ?Sized>(&self, visitor: &mut V) {
+    let mut path = vec![];
+    let mut stack = vec![(self, 0)];
+    visitor.enter(self, 0, &path);
+    while let Some((node, next)) = stack.last_mut() {
+      let node: &TreeNode = node;
+      match node.children().get(*next) {
+        Some(child) => {
+          path.push(*next);
+          *next += 1;
+          visitor.enter(child, path.len(), &path);
+          stack.push((child, 0));
+        }
+        None => {
+          stack.pop();
+          visitor.leave(node, path.len(), &path);
+          path.pop();
+        }
+      }
+    }
+  }
+
+  /// Returns the node at specified path of child node indexes.
+  pub fn get(&self, path: &[usize]) -> Option<&TreeNode> {
+    path.iter().try_fold(self, |node, index| node.child(*index))
+  }
+
+  /// Returns the mutable node at specified path of child node indexes.
+  pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+    path.iter().try_fold(self, |node, index| node.child_mut(*index))
+  }
+}
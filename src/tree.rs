@@ -1,24 +1,46 @@
 //! # Styled tree
 
 use crate::colors::{Color, ColorMode, RgbColor};
-use crate::text::{StyledText, Text};
-use std::fmt;
+use crate::options::RenderOptions;
+use crate::text::{strip_escapes, visible_width, StyledText, Text};
+use crate::writer::{IndentWriter, IoWriter};
 use std::fmt::Display;
+use std::rc::Rc;
+use std::{fmt, io, slice};
+
+/// Item of the tree waiting to be rendered.
+enum Item<'a> {
+  /// Node of the rendered tree.
+  Node(&'a TreeNode),
+  /// Summary line replacing hidden nodes.
+  Summary(Text),
+}
 
-const NONE: &str = "   ";
-const EDGE: &str = " └─";
-const PIPE: &str = " │ ";
-const FORK: &str = " ├─";
+/// Item waiting to be rendered, with its position in the tree.
+struct Entry<'a> {
+  /// Item to be rendered.
+  item: Item<'a>,
+  /// Guide connecting the node with its parent, none for the root node.
+  guide: Option<Guide>,
+  /// Length of the parent's prefix in the prefix buffer.
+  prefix_len: usize,
+  /// Depth of the node, the root node has depth zero.
+  depth: usize,
+}
 
-#[derive(Debug, Clone)]
-struct Level {
-  n: usize,
-  color: Color,
+/// Guide connecting a node with its parent.
+struct Guide {
+  /// Flag indicating if the node is the last child of its parent.
+  last: bool,
+  /// Escape sequence switching on the parent's color, shared by all child nodes of the parent.
+  color: Rc<str>,
+  /// Color mode of the parent.
   cm: ColorMode,
 }
 
 /// Types of nodes in styled tree.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TreeNode {
   /// Root or intermediary node in tree, always have one or mode child nodes.
   Node(Text, Vec<TreeNode>, Color, ColorMode),
@@ -33,69 +55,338 @@ impl Display for TreeNode {
 }
 
 impl TreeNode {
-  /// Writes node to provided writer.
-  pub fn write(&self, f: &mut dyn fmt::Write) -> fmt::Result {
-    Self::write_node(f, self, vec![])
+  /// Returns `true` when this is a [TreeNode::Node].
+  pub fn is_node(&self) -> bool {
+    matches!(self, TreeNode::Node(..))
   }
 
-  /// Writes node to provided writer with specified indentation.
-  pub fn write_indent(&self, f: &mut dyn fmt::Write, indent: usize) -> fmt::Result {
-    let mut tree = String::default();
-    Self::write_node(&mut tree, self, vec![])?;
-    let indent = " ".repeat(indent);
-    for line in tree.lines() {
-      writeln!(f, "{}{}", indent, line)?;
+  /// Returns `true` when this is a [TreeNode::Leaf].
+  pub fn is_leaf(&self) -> bool {
+    matches!(self, TreeNode::Leaf(..))
+  }
+
+  /// Returns the title of a node, leaf nodes have no title.
+  pub fn title(&self) -> Option<&Text> {
+    match self {
+      TreeNode::Node(title, _, _, _) => Some(title),
+      TreeNode::Leaf(_) => None,
+    }
+  }
+
+  /// Returns the mutable title of a node, leaf nodes have no title.
+  pub fn title_mut(&mut self) -> Option<&mut Text> {
+    match self {
+      TreeNode::Node(title, _, _, _) => Some(title),
+      TreeNode::Leaf(_) => None,
+    }
+  }
+
+  /// Replaces the title of a node and returns the previous one.
+  ///
+  /// Leaf nodes have no title, they are left unchanged and `None` is returned.
+  pub fn set_title(&mut self, title: Text) -> Option<Text> {
+    self.title_mut().map(|current| std::mem::replace(current, title))
+  }
+
+  /// Returns child nodes, leaf nodes have no child nodes.
+  pub fn children(&self) -> &[TreeNode] {
+    match self {
+      TreeNode::Node(_, children, _, _) => children,
+      TreeNode::Leaf(_) => &[],
+    }
+  }
+
+  /// Returns mutable child nodes, leaf nodes have no child nodes.
+  pub fn children_mut(&mut self) -> Option<&mut Vec<TreeNode>> {
+    match self {
+      TreeNode::Node(_, children, _, _) => Some(children),
+      TreeNode::Leaf(_) => None,
+    }
+  }
+
+  /// Returns the child node at specified index.
+  pub fn child(&self, index: usize) -> Option<&TreeNode> {
+    self.children().get(index)
+  }
+
+  /// Returns the mutable child node at specified index.
+  pub fn child_mut(&mut self, index: usize) -> Option<&mut TreeNode> {
+    self.children_mut().and_then(|children| children.get_mut(index))
+  }
+
+  /// Appends a child node.
+  ///
+  /// Leaf nodes have no child nodes, they are left unchanged and the child is returned as error,
+  /// use [TreeNode::make_node] first.
+  pub fn push_child(&mut self, child: TreeNode) -> Result<(), TreeNode> {
+    match self.children_mut() {
+      Some(children) => {
+        children.push(child);
+        Ok(())
+      }
+      None => Err(child),
+    }
+  }
+
+  /// Inserts a child node at specified index, shifting all child nodes after it.
+  ///
+  /// Leaf nodes are left unchanged and the child is returned as error.
+  ///
+  /// # Panics
+  ///
+  /// Panics when `index` is greater than the number of child nodes.
+  pub fn insert_child(&mut self, index: usize, child: TreeNode) -> Result<(), TreeNode> {
+    match self.children_mut() {
+      Some(children) => {
+        children.insert(index, child);
+        Ok(())
+      }
+      None => Err(child),
+    }
+  }
+
+  /// Removes and returns the child node at specified index, shifting all child nodes after it.
+  pub fn remove_child(&mut self, index: usize) -> Option<TreeNode> {
+    self.children_mut().filter(|children| index < children.len()).map(|children| children.remove(index))
+  }
+
+  /// Moves the child node from index `from` to index `to`, shifting child nodes in between.
+  ///
+  /// Returns `false` when this is a leaf node, which is left unchanged.
+  ///
+  /// # Panics
+  ///
+  /// Panics when any index is out of bounds.
+  pub fn move_child(&mut self, from: usize, to: usize) -> bool {
+    match self.children_mut() {
+      Some(children) => {
+        let child = children.remove(from);
+        children.insert(to, child);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Returns lines of a leaf node, nodes have no lines.
+  pub fn lines(&self) -> &[Text] {
+    match self {
+      TreeNode::Node(..) => &[],
+      TreeNode::Leaf(lines) => lines,
+    }
+  }
+
+  /// Returns mutable lines of a leaf node, nodes have no lines.
+  pub fn lines_mut(&mut self) -> Option<&mut Vec<Text>> {
+    match self {
+      TreeNode::Node(..) => None,
+      TreeNode::Leaf(lines) => Some(lines),
+    }
+  }
+
+  /// Appends a line to a leaf node.
+  ///
+  /// Nodes have no lines, they are left unchanged and the line is returned as error.
+  pub fn add_line(&mut self, line: Text) -> Result<(), Text> {
+    match self.lines_mut() {
+      Some(lines) => {
+        lines.push(line);
+        Ok(())
+      }
+      None => Err(line),
+    }
+  }
+
+  /// Turns a leaf node into a node with specified color.
+  ///
+  /// The first line of the leaf becomes the title of the node,
+  /// remaining lines, if any, become a leaf child node. Nodes are left unchanged.
+  pub fn make_node(&mut self, color: Color, cm: ColorMode) {
+    if let TreeNode::Leaf(lines) = self {
+      let mut lines = std::mem::take(lines).into_iter();
+      let title = lines.next().unwrap_or_else(|| Text::new(cm));
+      let rest = lines.collect::<Vec<Text>>();
+      let children = if rest.is_empty() { vec![] } else { vec![TreeNode::Leaf(rest)] };
+      *self = TreeNode::Node(title, children, color, cm);
+    }
+  }
+
+  /// Writes node to provided writer.
+  pub fn write(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+    self.write_with(f, &RenderOptions::default())
+  }
+
+  /// Writes node to provided writer using specified rendering options.
+  ///
+  /// Nodes are rendered using an explicit stack and a reused prefix buffer,
+  /// so trees of any depth can be rendered in time linear to the output size.
+  pub fn write_with<'a>(&'a self, f: &mut dyn fmt::Write, options: &RenderOptions) -> fmt::Result {
+    let glyphs = options.glyphs();
+    let gap = " ".repeat(options.gap);
+    let mut prefix = String::new();
+    let mut stack = vec![Entry {
+      item: Item::Node(self),
+      guide: None,
+      prefix_len: 0,
+      depth: 0,
+    }];
+    while let Some(entry) = stack.pop() {
+      // display guides
+      prefix.truncate(entry.prefix_len);
+      f.write_str(&prefix)?;
+      if let Some(guide) = &entry.guide {
+        let (first, next) = if guide.last { (&glyphs.edge, &glyphs.none) } else { (&glyphs.fork, &glyphs.pipe) };
+        write!(f, "{}{}{}", guide.color, first, guide.cm.clear())?;
+        prefix.push_str(&guide.color);
+        prefix.push_str(next);
+        prefix.push_str(guide.cm.clear());
+      }
+      let indent = if options.max_width.is_some() { visible_width(&prefix) } else { 0 };
+      // display lines and schedule child nodes
+      match entry.item {
+        Item::Node(TreeNode::Node(title, children, color, cm)) => {
+          let cm = &options.color_mode.unwrap_or(*cm);
+          let guide = if children.is_empty() { &glyphs.none } else { &glyphs.pipe };
+          let color_start: Rc<str> = cm.color(*color).into();
+          // continuation lines start after the guide, padded to the gap when the gap is wider
+          let guide_width = visible_width(guide);
+          let guide_padding = " ".repeat(options.gap.saturating_sub(guide_width));
+          let wrapped;
+          let lines = match options.max_width {
+            Some(max_width) => {
+              wrapped = title.wrap(max_width.saturating_sub(indent + guide_width.max(options.gap)));
+              &wrapped[..]
+            }
+            None => slice::from_ref(title),
+          };
+          for (i, line) in lines.iter().enumerate() {
+            match i {
+              0 => f.write_str(&gap)?,
+              _ => write!(f, "{}{}{}{}{}", prefix, color_start, guide, cm.clear(), guide_padding)?,
+            }
+            Self::write_line(f, line, options)?;
+          }
+          let summary = || Text::new(*cm).color(*color).italic();
+          let mut hidden = None;
+          let mut shown = children.len();
+          if options.max_depth.is_some_and(|max_depth| entry.depth >= max_depth) && !children.is_empty() {
+            let n = children.iter().map(TreeNode::height).max().unwrap_or(0);
+            hidden = Some(summary().s('[').s(n).space().plural("level", n).s(" collapsed]").clear());
+            shown = 0;
+          } else if let Some(max_children) = options.max_children.filter(|max_children| children.len() > *max_children) {
+            hidden = Some(summary().s("… ").s(children.len() - max_children).s(" more").clear());
+            shown = max_children;
+          }
+          let child_entry = |item: Item<'a>, last: bool| Entry {
+            item,
+            guide: Some(Guide {
+              last,
+              color: Rc::clone(&color_start),
+              cm: *cm,
+            }),
+            prefix_len: prefix.len(),
+            depth: entry.depth + 1,
+          };
+          // child nodes are pushed in reverse order, so the first child is rendered first
+          let hidden_entry = hidden.map(|summary| child_entry(Item::Summary(summary), true));
+          let last_shown = hidden_entry.is_none();
+          stack.extend(hidden_entry);
+          let mut last = last_shown;
+          for child in children[..shown].iter().rev() {
+            stack.push(child_entry(Item::Node(child), last));
+            last = false;
+          }
+        }
+        Item::Node(TreeNode::Leaf(lines)) => Self::write_leaf(f, lines, &prefix, &gap, options, indent)?,
+        Item::Summary(text) => Self::write_leaf(f, slice::from_ref(&text), &prefix, &gap, options, indent)?,
+      }
     }
     Ok(())
   }
 
-  /// Writes node.
-  fn write_node(f: &mut dyn fmt::Write, node: &TreeNode, levels: Vec<Level>) -> fmt::Result {
-    // display lines
-    let max_pos = levels.len();
-    let mut second_line = String::new();
-    for (pos, lev) in levels.iter().enumerate() {
-      let color = lev.cm.color(lev.color);
-      let clear = lev.cm.clear();
-      let last_row = pos == max_pos - 1;
-      if lev.n == 1 {
-        if !last_row {
-          write!(f, "{}{}{}", color, NONE, clear)?
-        } else {
-          write!(f, "{}{}{}", color, EDGE, clear)?
+  /// Writes lines of a leaf node, continuation lines are preceded with specified prefix.
+  fn write_leaf(f: &mut dyn fmt::Write, lines: &[Text], prefix: &str, gap: &str, options: &RenderOptions, indent: usize) -> fmt::Result {
+    let mut first = true;
+    for line in lines {
+      let wrapped;
+      let lines = match options.max_width {
+        Some(max_width) => {
+          wrapped = line.wrap(max_width.saturating_sub(indent + options.gap));
+          &wrapped[..]
         }
-        second_line.push_str(&format!("{}{}{}", color, NONE, clear));
-      } else {
-        if !last_row {
-          write!(f, "{}{}{}", color, PIPE, clear)?
+        None => slice::from_ref(line),
+      };
+      for line in lines {
+        if first {
+          first = false;
         } else {
-          write!(f, "{}{}{}", color, FORK, clear)?
+          f.write_str(prefix)?;
         }
-        second_line.push_str(&format!("{}{}{}", color, PIPE, clear));
+        f.write_str(gap)?;
+        Self::write_line(f, line, options)?;
       }
     }
-    // traverse child nodes
-    match node {
-      TreeNode::Node(title, children, color, cm) => {
-        let mut deep = children.len();
-        writeln!(f, " {}", title)?;
-        for node in children {
-          let mut level_next = levels.clone();
-          level_next.push(Level { n: deep, color: *color, cm: *cm });
-          deep -= 1;
-          Self::write_node(f, node, level_next)?;
-        }
+    Ok(())
+  }
+
+  /// Writes a single line of text followed by a newline.
+  fn write_line(f: &mut dyn fmt::Write, line: &Text, options: &RenderOptions) -> fmt::Result {
+    line.write(f, options.color_mode.unwrap_or(line.color_mode()))?;
+    f.write_char('\n')
+  }
+
+  /// Writes node to provided writer with specified indentation.
+  pub fn write_indent(&self, f: &mut dyn fmt::Write, indent: usize) -> fmt::Result {
+    self.write_with(&mut IndentWriter::new(f, indent), &RenderOptions::default())
+  }
+
+  /// Writes node line by line to provided I/O output.
+  ///
+  /// I/O errors, like a broken pipe, are returned to the caller.
+  pub fn write_io<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+    self.write_io_with(w, &RenderOptions::default())
+  }
+
+  /// Writes node line by line to provided I/O output using specified rendering options.
+  pub fn write_io_with<W: io::Write>(&self, w: &mut W, options: &RenderOptions) -> io::Result<()> {
+    let mut writer = IoWriter::new(w);
+    let result = self.write_with(&mut writer, options);
+    writer.finish(result)
+  }
+
+  /// Returns the rendered tree without any escape sequences, whatever color modes were used to build it.
+  pub fn to_plain_string(&self) -> String {
+    let mut output = String::new();
+    let _ = self.write_with(&mut output, &RenderOptions::default().color_mode(ColorMode::Off));
+    strip_escapes(&output)
+  }
+
+  /// Drops the tree iteratively, for trees too deep to be dropped recursively.
+  pub fn dispose(self) {
+    let mut stack = vec![self];
+    while let Some(mut node) = stack.pop() {
+      if let TreeNode::Node(_, children, _, _) = &mut node {
+        stack.append(children);
       }
-      TreeNode::Leaf(lines) => {
-        for (i, line) in lines.iter().enumerate() {
-          match i {
-            0 => writeln!(f, " {}", line)?,
-            _ => writeln!(f, "{} {}", second_line, line)?,
-          }
-        }
+    }
+  }
+
+  /// Returns the number of terminal columns taken by the widest line of the rendered tree.
+  pub fn width(&self) -> usize {
+    visible_width(&self.to_string())
+  }
+
+  /// Returns the number of levels in the tree, a single leaf has one level.
+  pub fn height(&self) -> usize {
+    let mut height = 0;
+    let mut stack = vec![(self, 1)];
+    while let Some((node, depth)) = stack.pop() {
+      height = height.max(depth);
+      if let TreeNode::Node(_, children, _, _) = node {
+        stack.extend(children.iter().map(|child| (child, depth + 1)));
       }
     }
-    Ok(())
+    height
   }
 }
 
@@ -344,6 +635,21 @@ impl StyledText for LeafLineBuilder {
     self.text = self.text.clear();
     self
   }
+
+  fn pad_left_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.pad_left_with(width, fill);
+    self
+  }
+
+  fn pad_right_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.pad_right_with(width, fill);
+    self
+  }
+
+  fn center_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.center_with(width, fill);
+    self
+  }
 }
 
 /// Builder for [TreeNode::Node].
@@ -615,4 +921,19 @@ impl StyledText for NodeLineBuilder {
     self.text = self.text.clear();
     self
   }
+
+  fn pad_left_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.pad_left_with(width, fill);
+    self
+  }
+
+  fn pad_right_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.pad_right_with(width, fill);
+    self
+  }
+
+  fn center_with(mut self, width: usize, fill: char) -> Self {
+    self.text = self.text.center_with(width, fill);
+    self
+  }
 }
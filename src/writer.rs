@@ -0,0 +1,88 @@
+//! # Writers used when rendering trees
+
+use std::{fmt, io};
+
+/// Adapter writing formatted output to [io::Write] line by line.
+///
+/// The first I/O error stops writing and is kept to be returned to the caller.
+pub(crate) struct IoWriter<'a, W: io::Write> {
+  /// Underlying output.
+  inner: &'a mut W,
+  /// Line being collected.
+  line: String,
+  /// The first I/O error reported by the underlying output.
+  error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> IoWriter<'a, W> {
+  pub(crate) fn new(inner: &'a mut W) -> Self {
+    Self {
+      inner,
+      line: String::new(),
+      error: None,
+    }
+  }
+
+  /// Writes the collected line to the underlying output.
+  fn flush_line(&mut self) -> fmt::Result {
+    if let Err(e) = self.inner.write_all(self.line.as_bytes()) {
+      self.error = Some(e);
+      return Err(fmt::Error);
+    }
+    self.line.clear();
+    Ok(())
+  }
+
+  /// Writes the remaining output and converts the formatting result into I/O result.
+  pub(crate) fn finish(mut self, result: fmt::Result) -> io::Result<()> {
+    if result.is_ok() && self.flush_line().is_ok() {
+      return Ok(());
+    }
+    Err(self.error.take().unwrap_or_else(|| io::Error::other("formatter error")))
+  }
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for piece in s.split_inclusive('\n') {
+      self.line.push_str(piece);
+      if piece.ends_with('\n') {
+        self.flush_line()?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Adapter inserting indentation at the beginning of every line.
+pub(crate) struct IndentWriter<'a> {
+  /// Underlying output.
+  inner: &'a mut dyn fmt::Write,
+  /// Indentation inserted before each line.
+  indent: String,
+  /// Flag indicating if the next write starts a new line.
+  line_start: bool,
+}
+
+impl<'a> IndentWriter<'a> {
+  pub(crate) fn new(inner: &'a mut dyn fmt::Write, indent: usize) -> Self {
+    Self {
+      inner,
+      indent: " ".repeat(indent),
+      line_start: true,
+    }
+  }
+}
+
+impl fmt::Write for IndentWriter<'_> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for piece in s.split_inclusive('\n') {
+      if self.line_start {
+        self.inner.write_str(&self.indent)?;
+      }
+      self.inner.write_str(piece)?;
+      self.line_start = piece.ends_with('\n');
+    }
+    Ok(())
+  }
+}
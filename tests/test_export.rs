@@ -0,0 +1,96 @@
+use antex::{leaf, node, Color, ColorMode, StyledText, TreeNode};
+
+fn tree() -> TreeNode {
+  let cm = ColorMode::Off;
+  node(Color::Blue, cm)
+    .line()
+    .bold()
+    .s("src")
+    .end()
+    .child(leaf(cm).line().s("main.rs").end().line().red().s("\"unsaved\"").end().end())
+    .child(node(Color::Long(196), cm).line().s("tests").end().end())
+    .end()
+}
+
+#[test]
+fn exporting_to_json_should_write_plain_text_and_styles() {
+  let expected = concat!(
+    r##"{"title":{"text":"src","spans":[{"text":"src","bold":true}]},"color":"#0000ee","children":["##,
+    r##"{"lines":[{"text":"main.rs","spans":[{"text":"main.rs"}]},{"text":"\"unsaved\"","spans":[{"text":"\"unsaved\"","fg":"#cd0000"}]}]},"##,
+    r##"{"title":{"text":"tests","spans":[{"text":"tests"}]},"color":"#ff0000","children":[]}]}"##
+  );
+  assert_eq!(expected, tree().to_json());
+}
+
+#[test]
+fn exporting_to_dot_should_color_nodes_and_edges() {
+  let expected = r##"digraph tree {
+  node [shape=box, fontname="monospace"];
+  n0 [label="src", color="#0000ee"];
+  n1 [label="main.rs\n\"unsaved\""];
+  n0 -> n1 [color="#0000ee"];
+  n2 [label="tests", color="#ff0000"];
+  n0 -> n2 [color="#0000ee"];
+}
+"##;
+  assert_eq!(expected, tree().to_dot());
+}
+
+#[test]
+fn exporting_to_mermaid_should_style_nodes_and_links() {
+  let expected = r##"graph TD
+  n0["src"]
+  n0 --> n1["main.rs<br/>#quot;unsaved#quot;"]
+  n0 --> n2["tests"]
+  style n0 stroke:#0000ee
+  linkStyle 0 stroke:#0000ee
+  linkStyle 1 stroke:#0000ee
+  style n2 stroke:#ff0000
+"##;
+  assert_eq!(expected, tree().to_mermaid());
+}
+
+#[test]
+fn exporting_to_outline_should_indent_children() {
+  assert_eq!("- src:\n  - main.rs\n  - \"unsaved\"\n  - tests:\n", tree().to_outline());
+  let cm = ColorMode::On;
+  let root = node(Color::Blue, cm)
+    .line()
+    .s("two\nlines")
+    .end()
+    .child(node(Color::Red, cm).line().s("a").end().child(leaf(cm).line().s("\u{1b}[4mb").end().end()).end())
+    .end();
+  assert_eq!("- two\n  lines:\n  - a:\n    - b\n", root.to_outline());
+  assert_eq!("- x\n- y\n", leaf(cm).line().s("x").end().line().s("y").end().end().to_outline());
+}
+
+#[test]
+fn colors_should_convert_to_palette_values() {
+  assert_eq!("#cd0000", Color::Red.to_hex());
+  assert_eq!("#5c5cff", Color::Long(12).to_hex());
+  assert_eq!("#87d7ff", Color::Long(117).to_hex());
+  assert_eq!("#eeeeee", Color::Long(255).to_hex());
+  assert_eq!((1, 2, 3), Color::Rgb((1, 2, 3)).to_rgb());
+}
+
+#[test]
+fn exporting_very_deep_tree_should_work() {
+  const DEPTH: usize = 100_000;
+  let cm = ColorMode::Off;
+  let mut root = leaf(cm).line().s("leaf").end().end();
+  for _ in 0..DEPTH {
+    root = node(Color::Yellow, cm).line().s("node").end().child(root).end();
+  }
+  let dot = root.to_dot();
+  assert_eq!(2 * DEPTH + 4, dot.lines().count());
+  assert!(dot.contains(&format!("  n{} -> n{} [color=\"#cdcd00\"];\n", DEPTH - 1, DEPTH)));
+  assert_eq!(3 * DEPTH + 2, root.to_mermaid().lines().count());
+  root.dispose();
+}
+
+#[test]
+fn exporting_to_json_should_strip_escape_sequences() {
+  let tree = leaf(ColorMode::On).line().s("\u{1b}[4mraw").end().end();
+  assert_eq!(r#"{"lines":[{"text":"raw","spans":[{"text":"raw"}]}]}"#, tree.to_json());
+  assert!(tree.to_dot().contains("[label=\"raw\"]"));
+}
@@ -0,0 +1,64 @@
+use antex::{dir_tree, ColorMode, DirTreeBuilder};
+use std::fs;
+use std::path::Path;
+
+fn create(root: &Path, files: &[&str]) {
+  for file in files {
+    let path = root.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    if file.ends_with('/') {
+      fs::create_dir_all(&path).unwrap();
+    } else {
+      fs::write(&path, "").unwrap();
+    }
+  }
+}
+
+fn render(builder: DirTreeBuilder, root: &Path) -> String {
+  builder.build(root).unwrap().to_string().replacen(&root.display().to_string(), "root", 1)
+}
+
+#[test]
+fn listing_directory_should_work() {
+  let dir = tempfile::tempdir().unwrap();
+  create(dir.path(), &["b.txt", "a/z.rs", "a/y.rs", "a/empty/", ".hidden", "c.rs"]);
+  let tree = dir_tree(dir.path(), ColorMode::Off).unwrap();
+  let output = tree.to_string().replacen(&dir.path().display().to_string(), "root", 1);
+  assert_eq!(" root\n ├─ a\n │  ├─ empty\n │  ├─ y.rs\n │  └─ z.rs\n ├─ b.txt\n └─ c.rs\n", output);
+  let output = render(DirTreeBuilder::new(ColorMode::Off).hidden(true).max_depth(1), dir.path());
+  assert_eq!(" root\n ├─ .hidden\n ├─ a\n ├─ b.txt\n └─ c.rs\n", output);
+  let output = render(DirTreeBuilder::new(ColorMode::Off).max_depth(0), dir.path());
+  assert_eq!(" root\n", output);
+}
+
+#[test]
+fn listing_directory_with_patterns_should_work() {
+  let dir = tempfile::tempdir().unwrap();
+  create(dir.path(), &["z.txt", "src/main.rs", "src/lib.rs", "target/debug/app", "a1.rs", "b2.rs"]);
+  let output = render(DirTreeBuilder::new(ColorMode::Off).include("*.rs").exclude("target").dirs_first(true), dir.path());
+  assert_eq!(" root\n ├─ src\n │  ├─ lib.rs\n │  └─ main.rs\n ├─ a1.rs\n └─ b2.rs\n", output);
+  let output = render(DirTreeBuilder::new(ColorMode::Off).include("[a-b]?.*").exclude("[!as]*"), dir.path());
+  assert_eq!(" root\n ├─ a1.rs\n └─ src\n", output);
+}
+
+#[cfg(unix)]
+#[test]
+fn listing_directory_with_symlinks_should_work() {
+  use std::os::unix::fs::{symlink, PermissionsExt};
+  let dir = tempfile::tempdir().unwrap();
+  create(dir.path(), &["data/file", "run.sh"]);
+  fs::set_permissions(dir.path().join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+  symlink(dir.path().join("data"), dir.path().join("link")).unwrap();
+  symlink(dir.path(), dir.path().join("data/loop")).unwrap();
+  let target = dir.path().join("data").display().to_string();
+  let output = render(DirTreeBuilder::new(ColorMode::Off), dir.path()).replace(&dir.path().display().to_string(), "root");
+  assert_eq!(" root\n ├─ data\n │  ├─ file\n │  └─ loop -> root\n ├─ link -> root/data\n └─ run.sh\n", output);
+  let output = render(DirTreeBuilder::new(ColorMode::Off).follow_symlinks(true), dir.path()).replace(&dir.path().display().to_string(), "root");
+  assert_eq!(
+    " root\n ├─ data\n │  ├─ file\n │  └─ loop -> root\n ├─ link -> root/data\n │  ├─ file\n │  └─ loop -> root\n └─ run.sh\n",
+    output
+  );
+  let colored = DirTreeBuilder::new(ColorMode::On).build(dir.path()).unwrap().to_string();
+  assert!(colored.contains("\u{1b}[32m\u{1b}[1mrun.sh\u{1b}[0m"));
+  assert!(colored.contains(&format!("\u{1b}[36mlink\u{1b}[0m -> {}", target)));
+}
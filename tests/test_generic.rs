@@ -0,0 +1,80 @@
+use antex::{Color, ColorMode, StyledText, Text, Tree, TreeRenderer};
+
+#[derive(Debug, Clone, PartialEq)]
+struct TestResult {
+  name: &'static str,
+  passed: bool,
+}
+
+fn results() -> Tree<TestResult> {
+  let result = |name, passed| Tree::new(TestResult { name, passed });
+  result("suite", false)
+    .child(result("module a", true).child(result("test 1", true)).child(result("test 2", true)))
+    .child(result("module b", false).child(result("test 3", false)))
+}
+
+struct Report(ColorMode);
+
+impl TreeRenderer<TestResult> for Report {
+  fn title(&self, value: &TestResult) -> Text {
+    Text::new(self.0).s(value.name)
+  }
+
+  fn lines(&self, value: &TestResult) -> Vec<Text> {
+    let status = if value.passed {
+      Text::new(self.0).green().s("ok")
+    } else {
+      Text::new(self.0).red().s("FAILED")
+    };
+    vec![Text::new(self.0).s(value.name).space() + status.clear()]
+  }
+
+  fn color(&self, value: &TestResult) -> Color {
+    if value.passed {
+      Color::Green
+    } else {
+      Color::Red
+    }
+  }
+}
+
+#[test]
+fn rendering_generic_tree_should_work() {
+  let tree = results();
+  let plain = tree.render(&Report(ColorMode::Off));
+  assert_eq!(
+    " suite\n ├─ module a\n │  ├─ test 1 ok\n │  └─ test 2 ok\n └─ module b\n    └─ test 3 FAILED\n",
+    plain.to_string()
+  );
+  let colored = tree.render(&Report(ColorMode::On)).to_string();
+  assert!(colored.contains("\u{1b}[31m └─\u{1b}[0m module b"));
+  let names = tree.render(&|value: &TestResult| Text::new(ColorMode::Off).s(value.name.to_uppercase()));
+  assert_eq!(" SUITE\n ├─ MODULE A\n │  ├─ TEST 1\n │  └─ TEST 2\n └─ MODULE B\n    └─ TEST 3\n", names.to_string());
+}
+
+#[test]
+fn filtering_and_sorting_generic_tree_should_work() {
+  let tree = results();
+  let failures = tree.filter(|value| !value.passed && value.name.starts_with("test")).unwrap();
+  assert_eq!(" suite\n └─ module b\n    └─ test 3 FAILED\n", failures.render(&Report(ColorMode::Off)).to_string());
+  let mut sorted = tree.map(|value| value.name.len() * 10 + value.passed as usize);
+  sorted.sort_by(|a, b| b.cmp(a));
+  assert_eq!(vec![81, 80], sorted.children.iter().map(|child| child.value).collect::<Vec<_>>());
+  assert!(tree.filter(|value| value.name == "missing").is_none());
+}
+
+#[test]
+fn disposing_very_deep_generic_tree_should_work() {
+  let mut root = Tree::new(0);
+  for i in 1..1_000_000 {
+    root = Tree::new(i).child(root);
+  }
+  root.dispose();
+}
+
+#[test]
+fn destructuring_generic_tree_by_value_should_work() {
+  let Tree { value, children } = results();
+  assert_eq!("suite", value.name);
+  assert_eq!(2, children.len());
+}
@@ -0,0 +1,38 @@
+use antex::{leaf, node, Color, ColorMode, RenderOptions, StyledText, Text};
+
+#[test]
+fn text_to_html_should_use_inline_styles() {
+  let text = Text::new(ColorMode::Off)
+    .s("a < b ")
+    .color_256(208)
+    .bold()
+    .s("warm")
+    .clear()
+    .bg_color_rgb((1, 2, 3))
+    .italic()
+    .underline()
+    .s("&");
+  assert_eq!(
+    "a &lt; b <span style=\"color:#ff8700;font-weight:bold\">warm</span><span style=\"background-color:#010203;font-style:italic;text-decoration:underline\">&amp;</span>",
+    text.to_html()
+  );
+  assert_eq!("plain", Text::new(ColorMode::On).s("plain").red().to_html());
+}
+
+#[test]
+fn tree_to_html_should_color_guides_in_pre_block() {
+  let cm = ColorMode::Off;
+  let tree = node(Color::Blue, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(leaf(cm).line().green().s("ok").end().end())
+    .child(leaf(cm).line().s("\u{1b}]8;;x\u{7}link").end().end())
+    .end();
+  assert_eq!(
+    "<pre> root\n<span style=\"color:#0000ee\"> ├─</span> <span style=\"color:#00cd00\">ok</span>\n<span style=\"color:#0000ee\"> └─</span> link\n</pre>",
+    tree.to_html()
+  );
+  let html = tree.to_html_with(&RenderOptions::new().max_children(1));
+  assert!(html.ends_with("<span style=\"color:#0000ee;font-style:italic\">… 1 more</span>\n</pre>"));
+}
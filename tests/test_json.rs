@@ -0,0 +1,31 @@
+#![cfg(feature = "json")]
+
+use antex::{json_tree, serde_tree, ColorMode};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[test]
+fn json_tree_should_list_members_and_items() {
+  let value = json!({"name": "antex", "version": 1.5, "stable": true, "license": null, "tags": ["tree", "ansi"], "meta": {}});
+  let expected = " {6}\n ├─ license: null\n ├─ meta: {}\n ├─ name: \"antex\"\n ├─ stable: true\n ├─ tags [2]\n │  ├─ [0]: \"tree\"\n │  └─ [1]: \"ansi\"\n └─ version: 1.5\n";
+  assert_eq!(expected, json_tree(&value, ColorMode::Off).to_string());
+  assert_eq!(" 42\n", json_tree(&json!(42), ColorMode::Off).to_string());
+}
+
+#[test]
+fn json_tree_should_color_scalars_by_type() {
+  let tree = json_tree(&json!(["a", 1, false]), ColorMode::On);
+  let output = tree.to_string();
+  assert!(output.contains("[0]: \u{1b}[32m\"a\"\u{1b}[0m"));
+  assert!(output.contains("[1]: \u{1b}[36m1\u{1b}[0m"));
+  assert!(output.contains("[2]: \u{1b}[33mfalse\u{1b}[0m"));
+}
+
+#[test]
+fn serde_tree_should_render_serializable_values() {
+  let mut deps = BTreeMap::new();
+  deps.insert("serde", vec![1, 0]);
+  deps.insert("libc", vec![]);
+  let expected = " {2}\n ├─ libc: []\n └─ serde [2]\n    ├─ [0]: 1\n    └─ [1]: 0\n";
+  assert_eq!(expected, serde_tree(&deps, ColorMode::Off).unwrap().to_string());
+}
@@ -0,0 +1,24 @@
+use antex::{outline, Color, ColorMode, OutlineBuilder};
+
+#[test]
+fn parsing_indented_text_should_work() {
+  let input = "project\n  src\n    main.rs\n    lib.rs\n\n  Cargo.toml\nnotes\n";
+  let trees = outline(input, Color::White, ColorMode::Off);
+  assert_eq!(2, trees.len());
+  assert_eq!(" project\n ├─ src\n │  ├─ main.rs\n │  └─ lib.rs\n └─ Cargo.toml\n", trees[0].to_string());
+  assert_eq!(" notes\n", trees[1].to_string());
+  assert!(trees[1].is_leaf());
+}
+
+#[test]
+fn parsing_markdown_lists_and_tabs_should_work() {
+  let input = "- build\n  * compile\n    1. parse\n    2) check\n  + link\n-\ttest\n";
+  let trees = outline(input, Color::White, ColorMode::Off);
+  assert_eq!(" build\n ├─ compile\n │  ├─ parse\n │  └─ check\n └─ link\n", trees[0].to_string());
+  assert_eq!(" test\n", trees[1].to_string());
+  let input = "root\n\tchild\n\t\tgrandchild\n        sibling of grandchild\n";
+  let trees = outline(input, Color::White, ColorMode::Off);
+  assert_eq!(" root\n └─ child\n    ├─ grandchild\n    └─ sibling of grandchild\n", trees[0].to_string());
+  let trees = OutlineBuilder::new(Color::White, ColorMode::Off).tab_width(8).build(input);
+  assert_eq!(" root\n ├─ child\n │  └─ grandchild\n └─ sibling of grandchild\n", trees[0].to_string());
+}
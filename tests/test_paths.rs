@@ -0,0 +1,28 @@
+use antex::{path_tree, Color, ColorMode, PathTreeBuilder, StyledText, Text};
+
+#[test]
+fn building_tree_from_paths_should_merge_prefixes() {
+  let paths = ["src/tree.rs", "src/text.rs", "tests/test_tree.rs", "/src//main.rs", "Cargo.toml"];
+  let trees = path_tree(paths, Color::White, ColorMode::Off);
+  assert_eq!(3, trees.len());
+  assert_eq!(" src\n ├─ tree.rs\n ├─ text.rs\n └─ main.rs\n", trees[0].to_string());
+  assert_eq!(" tests\n └─ test_tree.rs\n", trees[1].to_string());
+  assert_eq!(" Cargo.toml\n", trees[2].to_string());
+}
+
+#[test]
+fn building_tree_from_paths_with_values_should_work() {
+  let cm = ColorMode::Off;
+  let entries = vec![
+    ("antex::tree::tests::building", Some(Text::new(cm).green().s("ok"))),
+    ("antex::tree::tests::wrapping", Some(Text::new(cm).red().s("FAILED"))),
+    ("antex::text", None),
+    ("antex::text::width", Some(Text::new(cm).s("ignored"))),
+  ];
+  let trees = PathTreeBuilder::new(Color::White, cm).separator("::").build_with_values(entries);
+  assert_eq!(1, trees.len());
+  assert_eq!(
+    " antex\n ├─ tree\n │  └─ tests\n │     ├─ building ok\n │     └─ wrapping FAILED\n └─ text\n    └─ width ignored\n",
+    trees[0].to_string()
+  );
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "serde")]
+
+use antex::{leaf, node, Color, ColorMode, StyledText, Text, TreeNode};
+
+#[test]
+fn serializing_tree_to_json_should_work() {
+  let cm = ColorMode::On;
+  let root = node(Color::Rgb((1, 2, 3)), cm)
+    .line()
+    .bold()
+    .s("suite")
+    .end()
+    .child(leaf(cm).line().color_256(100).s("ok").clear().end().end())
+    .end();
+  let json = serde_json::to_string(&root).unwrap();
+  let restored: TreeNode = serde_json::from_str(&json).unwrap();
+  assert_eq!(root.to_string(), restored.to_string());
+  let text = Text::new(cm).red().s("a");
+  assert_eq!(
+    r#"{"color_mode":"On","spans":[{"style":{"fg":"Red","bg":null,"bold":false,"italic":false,"underline":false},"content":"a"}],"style":{"fg":"Red","bg":null,"bold":false,"italic":false,"underline":false}}"#,
+    serde_json::to_string(&text).unwrap()
+  );
+}
+
+#[test]
+fn deserializing_text_from_plain_content_or_spans_should_work() {
+  let text: Text = serde_json::from_str(r#""plain content""#).unwrap();
+  assert_eq!(ColorMode::Off, text.color_mode());
+  assert_eq!("plain content", text.with_color_mode(ColorMode::On).to_string());
+  let text: Text = serde_json::from_str(r#"{"color_mode":"On","spans":[{"style":{"fg":{"Long":200}},"content":"x"},{"content":"y"}]}"#).unwrap();
+  assert_eq!("\u{1b}[38;5;200mx\u{1b}[0my", text.to_string());
+  let root: TreeNode = serde_json::from_str(r#"{"Node":["root",[{"Leaf":["a","b"]}],"Yellow","Off"]}"#).unwrap();
+  assert_eq!(" root\n └─ a\n    b\n", root.to_string());
+}
@@ -0,0 +1,31 @@
+use antex::{leaf, node, Color, ColorMode, StyledText, SvgTheme, Text};
+
+#[test]
+fn text_to_svg_should_place_spans_on_grid() {
+  let text = Text::new(ColorMode::Off).s("ok ").color_256(208).bold().s("<warm>").clear().bg_red().s(" ");
+  let expected = r##"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="36.8" viewBox="0 0 104 36.8" font-family="monospace" font-size="14">
+<rect width="100%" height="100%" fill="#1e1e1e"/>
+<rect x="85.6" y="10" width="8.4" height="16.8" fill="#cd0000"/>
+<text y="23.3" xml:space="preserve"><tspan x="10" fill="#e5e5e5">ok </tspan><tspan x="35.2" fill="#ff8700" font-weight="bold">&lt;warm&gt;</tspan></text>
+</svg>
+"##;
+  assert_eq!(expected, text.to_svg(&SvgTheme::new()));
+}
+
+#[test]
+fn tree_to_svg_should_use_theme() {
+  let cm = ColorMode::On;
+  let tree = node(Color::Blue, cm).line().s("root").end().child(leaf(cm).line().s("日本").end().end()).end();
+  let theme = SvgTheme::light()
+    .background(Color::Rgb((250, 250, 240)))
+    .font_family("Fira Code, monospace")
+    .font_size(10.0)
+    .padding(0.0);
+  let expected = r##"<svg xmlns="http://www.w3.org/2000/svg" width="48" height="24" viewBox="0 0 48 24" font-family="Fira Code, monospace" font-size="10">
+<rect width="100%" height="100%" fill="#fafaf0"/>
+<text y="9.5" xml:space="preserve"><tspan x="6" fill="#000000">root</tspan></text>
+<text y="21.5" xml:space="preserve"><tspan x="0" fill="#0000ee"> └─</tspan><tspan x="24" fill="#000000">日本</tspan></text>
+</svg>
+"##;
+  assert_eq!(expected, tree.to_svg(&theme));
+}
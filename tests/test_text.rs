@@ -0,0 +1,197 @@
+use antex::{visible_width, Color, ColorMode, Span, Style, StyledText, Text};
+
+#[test]
+fn text_should_render_in_any_color_mode() {
+  let text = Text::new(ColorMode::On).s("Hello ").red().bold().s("world").clear().s("!");
+  assert_eq!("Hello \u{1b}[31m\u{1b}[1mworld\u{1b}[0m!", text.to_string());
+  assert_eq!("Hello world!", text.clone().with_color_mode(ColorMode::Off).to_string());
+  let mut plain = String::new();
+  let _ = text.write(&mut plain, ColorMode::Off);
+  assert_eq!("Hello world!", plain);
+}
+
+#[test]
+fn text_should_store_styled_spans() {
+  let text = Text::new(ColorMode::Off).s("a").s("b").green().s("c").bg_color_256(100).underline().s("d");
+  let green = Style {
+    fg: Some(Color::Green),
+    ..Default::default()
+  };
+  let expected = vec![
+    Span::new(Style::default(), "ab"),
+    Span::new(green, "c"),
+    Span::new(
+      Style {
+        bg: Some(Color::Long(100)),
+        underline: true,
+        ..green
+      },
+      "d",
+    ),
+  ];
+  assert_eq!(expected, text.spans());
+}
+
+#[test]
+fn adding_texts_should_keep_styles() {
+  let cm = ColorMode::On;
+  let text = Text::new(cm).red().s("a") + Text::new(cm).s("b").blue().s("c");
+  assert_eq!("\u{1b}[31ma\u{1b}[0mb\u{1b}[34mc", text.to_string());
+}
+
+#[test]
+fn width_should_count_visible_columns() {
+  let text = Text::new(ColorMode::On).red().s("abc").bold().s("日本").clear().s("👍");
+  assert_eq!(9, text.width());
+  assert_eq!(2, Text::new(ColorMode::On).s("ab").nl().green().s("c").width());
+  assert_eq!(0, Text::new(ColorMode::On).red().width());
+}
+
+#[test]
+fn visible_width_should_skip_escape_sequences() {
+  assert_eq!(5, visible_width("\u{1b}[38;5;100mhello\u{1b}[0m"));
+  assert_eq!(4, visible_width("\u{1b}]8;;http://x\u{7}link\u{1b}]8;;\u{1b}\\"));
+  assert_eq!(3, visible_width("a\nbcd\r\n"));
+}
+
+#[test]
+fn padding_should_use_visible_width() {
+  let cm = ColorMode::On;
+  assert_eq!("  \u{1b}[31mab", Text::new(cm).red().s("ab").pad_left(4).to_string());
+  assert_eq!("\u{1b}[31mab\u{1b}[0m  \u{1b}[31m", Text::new(cm).red().s("ab").pad_right(4).to_string());
+  assert_eq!("..日本...", Text::new(cm).s("日本").center_with(9, '.').to_string());
+  assert_eq!("abc", Text::new(cm).s("abc").pad_left(2).to_string());
+  let row = Text::new(ColorMode::Off).green().s("name").pad_right(8).s("value").pad_right(15).s("|");
+  assert_eq!("name    value  |", row.to_string());
+}
+
+#[test]
+fn truncate_should_cut_to_visible_width() {
+  let cm = ColorMode::On;
+  let text = Text::new(cm).s("path/").red().s("to/file.rs");
+  assert_eq!("path/\u{1b}[31mto/f…\u{1b}[0m", text.truncate(10, "…").to_string());
+  assert_eq!(10, text.truncate(10, "…").width());
+  assert_eq!("pat…", text.truncate(4, "…").with_color_mode(ColorMode::Off).to_string());
+  assert_eq!(text.to_string(), text.truncate(15, "…").to_string());
+}
+
+#[test]
+fn truncate_should_not_split_graphemes_and_escapes() {
+  let text = Text::new(ColorMode::Off).s("e\u{301}日本\u{1b}[1mabc");
+  assert_eq!("e\u{301}日…", text.truncate(5, "…").to_string());
+  assert_eq!("e\u{301}…", text.truncate(3, "…").to_string());
+  let cm = ColorMode::On;
+  assert_eq!("\u{1b}[31mab…\u{1b}[0m", Text::new(cm).s("\u{1b}[31mabcdef").truncate(3, "…").to_string());
+  assert_eq!("ab\u{1b}[1m…\u{1b}[0m", Text::new(cm).s("ab\u{1b}[1mcdef").truncate(3, "…").to_string());
+  let lines = Text::new(ColorMode::Off).s("short").nl().s("much longer line");
+  assert_eq!("short\nmuch..", lines.truncate(6, "..").to_string());
+}
+
+#[test]
+fn wrap_should_break_on_word_boundaries() {
+  let text = Text::new(ColorMode::Off).s("assertion failed: left == right");
+  let lines = text.wrap(12).iter().map(|line| line.to_string()).collect::<Vec<String>>();
+  assert_eq!(vec!["assertion", "failed: left", "== right"], lines);
+  let lines = Text::new(ColorMode::Off)
+    .s("  ab abcdefghij kl")
+    .nl()
+    .s("m")
+    .wrap(4)
+    .iter()
+    .map(|line| line.to_string())
+    .collect::<Vec<String>>();
+  assert_eq!(vec!["  ab", "abcd", "efgh", "ij", "kl", "m"], lines);
+}
+
+#[test]
+fn wrap_should_carry_style_over() {
+  let cm = ColorMode::On;
+  let text = Text::new(cm).s("one ").red().s("two three").clear().s(" four");
+  let lines = text.wrap(9).iter().map(|line| line.to_string()).collect::<Vec<String>>();
+  assert_eq!(vec!["one \u{1b}[31mtwo\u{1b}[0m", "\u{1b}[31mthree\u{1b}[0m", "four"], lines);
+  assert!(text.wrap(9).iter().all(|line| line.width() <= 9));
+}
+
+#[test]
+fn from_ansi_should_parse_sgr_sequences() {
+  let text = Text::from_ansi("a\u{1b}[1;31mb\u{1b}[22;92mc\u{1b}[38;5;208;48;2;1;2;3md\u{1b}[0;3;4me\u{1b}[me\u{1b}]8;;x\u{7}f\u{1b}[44m");
+  let style = |f: fn(&mut Style)| {
+    let mut style = Style::default();
+    f(&mut style);
+    style
+  };
+  let expected = vec![
+    Span::new(Style::default(), "a"),
+    Span::new(style(|s| (s.fg, s.bold) = (Some(Color::Red), true)), "b"),
+    Span::new(style(|s| s.fg = Some(Color::Long(10))), "c"),
+    Span::new(style(|s| (s.fg, s.bg) = (Some(Color::Long(208)), Some(Color::Rgb((1, 2, 3))))), "d"),
+    Span::new(style(|s| (s.italic, s.underline) = (true, true)), "e"),
+    Span::new(Style::default(), "ef"),
+  ];
+  assert_eq!(expected, text.spans());
+  assert_eq!(style(|s| s.bg = Some(Color::Blue)), text.style());
+  assert_eq!(7, text.width());
+}
+
+#[test]
+fn from_ansi_should_round_trip_rendered_text() {
+  let text = Text::new(ColorMode::On)
+    .s("x ")
+    .color_rgb((9, 8, 7))
+    .bg_magenta()
+    .bold()
+    .s("y")
+    .clear()
+    .underline()
+    .s("z")
+    .clear();
+  assert_eq!(text.spans(), Text::from_ansi(&text.to_string()).spans());
+  assert_eq!("plain", Text::from_ansi("plain").with_color_mode(ColorMode::On).to_string());
+  let text = Text::from_ansi("\u{1b}[31mred");
+  assert_eq!(ColorMode::Off, text.color_mode());
+  assert_eq!("red", text.to_string());
+  assert_eq!("\u{1b}[31mred", text.with_color_mode(ColorMode::On).to_string());
+}
+
+#[test]
+fn plain_should_strip_all_escape_sequences() {
+  let text = Text::new(ColorMode::On).red().s("error").clear().s(": \u{1b}]8;;http://x\u{7}link\u{1b}]8;;\u{1b}\\ ") + Text::from_ansi("\u{1b}[1mbold");
+  assert_eq!("error: link bold", text.plain());
+  assert_eq!(text.plain(), text.clone().with_color_mode(ColorMode::Off).plain());
+}
+
+#[test]
+fn from_ansi_should_ignore_malformed_parameters() {
+  let bold = Style { bold: true, ..Default::default() };
+  assert_eq!(vec![Span::new(bold, "X")], Text::from_ansi("\u{1b}[1m\u{1b}[99999;?mX").spans());
+  let italic = Style {
+    italic: true,
+    ..Default::default()
+  };
+  assert_eq!(vec![Span::new(italic, "X")], Text::from_ansi("\u{1b}[1;;3mX").spans());
+  assert_eq!(vec![Span::new(bold, "X")], Text::from_ansi("\u{1b}[1;38;5mX").spans());
+}
+
+#[test]
+fn from_ansi_should_parse_colon_separated_colors() {
+  let text = Text::from_ansi("\u{1b}[1m\u{1b}[38:5:208mX\u{1b}[48:2::1:2:3mY\u{1b}[38:2:4:5:6;4:0mZ");
+  let x = Style {
+    fg: Some(Color::Long(208)),
+    bold: true,
+    ..Default::default()
+  };
+  let y = Style {
+    bg: Some(Color::Rgb((1, 2, 3))),
+    ..x
+  };
+  let z = Style {
+    fg: Some(Color::Rgb((4, 5, 6))),
+    ..y
+  };
+  assert_eq!(vec![Span::new(x, "X"), Span::new(y, "Y"), Span::new(z, "Z")], text.spans());
+  let underline = Style {
+    underline: true,
+    ..Default::default()
+  };
+  assert_eq!(vec![Span::new(underline, "U")], Text::from_ansi("\u{1b}[4:3mU").spans());
+}
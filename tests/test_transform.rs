@@ -0,0 +1,54 @@
+use antex::{leaf, node, Color, ColorMode, StyledText, TreeNode};
+
+fn tree(cm: ColorMode) -> TreeNode {
+  node(Color::Yellow, cm)
+    .line()
+    .s("suite")
+    .end()
+    .child(
+      node(Color::Yellow, cm)
+        .line()
+        .s("module b")
+        .end()
+        .child(leaf(cm).line().green().s("test z ok").clear().end().end())
+        .child(leaf(cm).line().red().s("test y FAILED").clear().end().line().s("left != right").end().end())
+        .end(),
+    )
+    .child(
+      node(Color::Yellow, cm)
+        .line()
+        .s("module a")
+        .end()
+        .child(leaf(cm).line().green().s("test x ok").clear().end().end())
+        .end(),
+    )
+    .end()
+}
+
+#[test]
+fn label_should_return_plain_text() {
+  let root = tree(ColorMode::On);
+  assert_eq!("suite", root.label());
+  assert_eq!("test y FAILED\nleft != right", root.get(&[0, 1]).unwrap().label());
+}
+
+#[test]
+fn sorting_tree_should_work() {
+  let mut root = tree(ColorMode::On);
+  root.sort_by_key(|node| node.label());
+  let plain = root.pre_order().map(|visit| visit.node.label()).collect::<Vec<_>>().join("|");
+  assert_eq!("suite|module a|test x ok|module b|test y FAILED\nleft != right|test z ok", plain);
+  root.sort_by(|a, b| b.label().cmp(&a.label()));
+  assert_eq!("module b", root.child(0).unwrap().label());
+}
+
+#[test]
+fn filtering_tree_should_keep_ancestors() {
+  let root = tree(ColorMode::Off);
+  let failures = root.filter(|node| node.label().contains("FAILED")).unwrap();
+  assert_eq!(" suite\n └─ module b\n    └─ test y FAILED\n       left != right\n", failures.to_string());
+  assert!(root.filter(|node| node.label() == "missing").is_none());
+  let modules = root.filter(|node| node.label().starts_with("module")).unwrap();
+  assert_eq!(2, modules.children().len());
+  assert!(modules.children().iter().all(|module| module.children().is_empty()));
+}
@@ -0,0 +1,84 @@
+use antex::{leaf, node, Color, ColorMode, StyledText, TreeNode, TreeVisitor};
+
+fn label(node: &TreeNode) -> String {
+  node.title().unwrap_or_else(|| &node.lines()[0]).to_string()
+}
+
+fn tree() -> TreeNode {
+  let cm = ColorMode::Off;
+  node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(
+      node(Color::Yellow, cm)
+        .line()
+        .s("a")
+        .end()
+        .child(leaf(cm).line().s("a1").end().end())
+        .child(leaf(cm).line().s("a2").end().end())
+        .end(),
+    )
+    .child(leaf(cm).line().s("b").end().end())
+    .end()
+}
+
+#[test]
+fn pre_order_should_visit_parents_first() {
+  let root = tree();
+  let visits = root.pre_order().map(|visit| (label(visit.node), visit.depth, visit.path)).collect::<Vec<_>>();
+  let expected = vec![
+    ("root".to_string(), 0, vec![]),
+    ("a".to_string(), 1, vec![0]),
+    ("a1".to_string(), 2, vec![0, 0]),
+    ("a2".to_string(), 2, vec![0, 1]),
+    ("b".to_string(), 1, vec![1]),
+  ];
+  assert_eq!(expected, visits);
+}
+
+#[test]
+fn post_order_and_breadth_first_should_work() {
+  let root = tree();
+  let post_order = root.post_order().map(|visit| label(visit.node)).collect::<Vec<_>>();
+  assert_eq!(vec!["a1", "a2", "a", "b", "root"], post_order);
+  let paths = root.post_order().map(|visit| visit.path).collect::<Vec<_>>();
+  assert_eq!(vec![vec![0, 0], vec![0, 1], vec![0], vec![1], vec![]], paths);
+  let breadth_first = root.breadth_first().map(|visit| label(visit.node)).collect::<Vec<_>>();
+  assert_eq!(vec!["root", "a", "b", "a1", "a2"], breadth_first);
+  assert_eq!(3, root.pre_order().filter(|visit| visit.node.is_leaf()).count());
+  let found = root.pre_order().find(|visit| label(visit.node) == "a2").unwrap();
+  assert_eq!("a2", label(root.get(&found.path).unwrap()));
+}
+
+#[test]
+fn visitor_should_enter_and_leave_nodes() {
+  #[derive(Default)]
+  struct Recorder(Vec<String>);
+
+  impl TreeVisitor for Recorder {
+    fn enter(&mut self, node: &TreeNode, depth: usize, path: &[usize]) {
+      self.0.push(format!("enter {} {} {:?}", label(node), depth, path));
+    }
+
+    fn leave(&mut self, node: &TreeNode, _depth: usize, _path: &[usize]) {
+      self.0.push(format!("leave {}", label(node)));
+    }
+  }
+
+  let mut recorder = Recorder::default();
+  tree().walk(&mut recorder);
+  let expected = vec![
+    "enter root 0 []",
+    "enter a 1 [0]",
+    "enter a1 2 [0, 0]",
+    "leave a1",
+    "enter a2 2 [0, 1]",
+    "leave a2",
+    "leave a",
+    "enter b 1 [1]",
+    "leave b",
+    "leave root",
+  ];
+  assert_eq!(expected, recorder.0);
+}
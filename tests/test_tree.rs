@@ -1,6 +1,23 @@
-use antex::{leaf, node, Color, ColorMode, StyledText};
+use antex::{leaf, node, visible_width, Color, ColorMode, RenderOptions, StyledText, Text, TreeNode, TreeStyle};
 use std::fmt::Write;
 
+fn tree() -> TreeNode {
+  let cm = ColorMode::Off;
+  node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(node(Color::Yellow, cm).line().s("a").end().child(leaf(cm).line().s("b").end().end()).end())
+    .child(leaf(cm).line().s("c").end().line().s("d").end().end())
+    .end()
+}
+
+fn render(tree: &TreeNode, options: &RenderOptions) -> String {
+  let mut output = String::new();
+  let _ = tree.write_with(&mut output, options);
+  output
+}
+
 #[test]
 fn building_tree_should_work() {
   const EXPECTED: &str = r#"
@@ -141,3 +158,277 @@ fn building_tree_should_work() {
   let _ = root.write(&mut output);
   assert_eq!(EXPECTED, output);
 }
+
+#[test]
+fn tree_width_should_count_widest_line() {
+  let cm = ColorMode::On;
+  let root = node(Color::Yellow, cm)
+    .line()
+    .red()
+    .s("root")
+    .end()
+    .child(node(Color::Cyan, cm).line().s("node").end().child(leaf(cm).line().bold().s("日本語").end().end()).end())
+    .end();
+  assert_eq!(13, root.width());
+}
+
+#[test]
+fn padding_tree_lines_should_work() {
+  let cm = ColorMode::Off;
+  let root = node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .pad_left(6)
+    .end()
+    .child(leaf(cm).line().s("a").pad_right(4).s("|").end().line().s("b").center_with(5, '-').end().end())
+    .end();
+  assert_eq!("   root\n └─ a   |\n    --b--\n", root.to_string());
+}
+
+#[test]
+fn wrapping_tree_lines_should_keep_guides() {
+  const EXPECTED: &str = r#" suite with a long
+ │ title
+ ├─ test one failed:
+ │  left differs from
+ │  right
+ └─ ok
+"#;
+
+  let cm = ColorMode::Off;
+  let root = node(Color::Yellow, cm)
+    .line()
+    .s("suite with a long title")
+    .end()
+    .child(leaf(cm).line().s("test one failed: left differs from right").end().end())
+    .child(leaf(cm).line().s("ok").end().end())
+    .end();
+  let output = render(&root, &RenderOptions::new().max_width(22));
+  assert_eq!(EXPECTED, output);
+  assert!(output.lines().all(|line| visible_width(line) <= 22));
+}
+
+#[test]
+fn wrapping_titles_should_fit_gap_wider_than_guide() {
+  let cm = ColorMode::Off;
+  let root = node(Color::Yellow, cm).line().s("aaaaaaa bbb").end().child(leaf(cm).line().s("ok").end().end()).end();
+  let output = render(&root, &RenderOptions::new().max_width(10).gap(4));
+  assert_eq!("    aaaaaa\n │  a bbb\n └─    ok\n", output);
+  assert!(output.lines().all(|line| visible_width(line) <= 10));
+}
+
+#[test]
+fn rendering_with_tree_styles_should_work() {
+  let root = tree();
+  let render = |tree_style: TreeStyle| render(&root, &RenderOptions::new().tree_style(tree_style));
+  assert_eq!(" root\n|-- a\n|  `-- b\n`-- c\n    d\n", render(TreeStyle::ascii()));
+  assert_eq!(" root\n ┣━ a\n ┃  ┗━ b\n ┗━ c\n    d\n", render(TreeStyle::heavy()));
+  assert_eq!(" root\n ╠═ a\n ║  ╚═ b\n ╚═ c\n    d\n", render(TreeStyle::double()));
+  assert_eq!(" root\n ├─ a\n │  ╰─ b\n ╰─ c\n    d\n", render(TreeStyle::rounded()));
+  assert_eq!(" root\n + a\n : - b\n - c\n   d\n", render(TreeStyle::new("  ", " -", " :", " +")));
+}
+
+#[test]
+fn rendering_with_level_width_and_gap_should_work() {
+  let root = tree();
+  assert_eq!("root\n├─a\n│ └─b\n└─c\n  d\n", render(&root, &RenderOptions::new().level_width(2).gap(0)));
+  assert_eq!(
+    "  root\n ├───  a\n │    └───  b\n └───  c\n       d\n",
+    render(&root, &RenderOptions::new().level_width(5).gap(2))
+  );
+  assert_eq!(
+    " root\n|---- a\n|    `---- b\n`---- c\n      d\n",
+    render(&root, &RenderOptions::new().tree_style(TreeStyle::ascii()).level_width(5))
+  );
+}
+
+#[test]
+fn rendering_with_depth_and_children_limits_should_work() {
+  let cm = ColorMode::Off;
+  let mut suite = node(Color::Yellow, cm).line().s("suite").end();
+  for i in 1..=5 {
+    suite.add_child(leaf(cm).line().s(format!("test {i}")).end().end());
+  }
+  let root = node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(node(Color::Yellow, cm).line().s("group").end().child(suite.end()).end())
+    .child(leaf(cm).line().s("last").end().end())
+    .end();
+  assert_eq!(
+    " root\n ├─ group\n │  └─ suite\n │     ├─ test 1\n │     ├─ test 2\n │     └─ … 3 more\n └─ last\n",
+    render(&root, &RenderOptions::new().max_children(2))
+  );
+  assert_eq!(
+    " root\n ├─ group\n │  └─ [2 levels collapsed]\n └─ last\n",
+    render(&root, &RenderOptions::new().max_depth(1))
+  );
+  assert_eq!(" root\n └─ [3 levels collapsed]\n", render(&root, &RenderOptions::new().max_depth(0)));
+  assert_eq!(
+    " root\n ├─ group\n │  └─ suite\n │     ├─ test 1\n │     └─ … 4 more\n └─ … 1 more\n",
+    render(&root, &RenderOptions::new().max_children(1))
+  );
+  assert_eq!(4, root.height());
+}
+
+#[test]
+fn writing_tree_to_io_should_work() {
+  let cm = ColorMode::Off;
+  let root = node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(leaf(cm).line().s("a").end().line().s("b").end().end())
+    .end();
+  let mut output = Vec::new();
+  root.write_io(&mut output).unwrap();
+  assert_eq!(root.to_string().as_bytes(), output);
+  let mut indented = String::new();
+  let _ = root.write_indent(&mut indented, 2);
+  assert_eq!("   root\n   └─ a\n      b\n", indented);
+}
+
+#[test]
+fn writing_tree_to_broken_pipe_should_return_error() {
+  struct BrokenPipe(usize);
+
+  impl std::io::Write for BrokenPipe {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+      if self.0 == 0 {
+        return Err(std::io::ErrorKind::BrokenPipe.into());
+      }
+      self.0 -= 1;
+      Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+      Ok(())
+    }
+  }
+
+  let cm = ColorMode::Off;
+  let root = node(Color::Yellow, cm)
+    .line()
+    .s("root")
+    .end()
+    .child(leaf(cm).line().s("a").end().line().s("b").end().end())
+    .end();
+  let error = root.write_io(&mut BrokenPipe(2)).unwrap_err();
+  assert_eq!(std::io::ErrorKind::BrokenPipe, error.kind());
+}
+
+#[test]
+fn rendering_very_deep_tree_should_work() {
+  struct Counter(usize, usize);
+
+  impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+      self.0 += s.len();
+      self.1 += s.matches('\n').count();
+      Ok(())
+    }
+  }
+
+  const DEPTH: usize = 10_000;
+  let cm = ColorMode::Off;
+  let mut root = leaf(cm).line().s("leaf").end().end();
+  for _ in 0..DEPTH {
+    root = node(Color::Yellow, cm).line().s("node").end().child(root).end();
+  }
+  let mut counter = Counter(0, 0);
+  let _ = root.write(&mut counter);
+  assert_eq!(DEPTH + 1, counter.1);
+  assert_eq!(DEPTH + 1, root.height());
+  // dismantle the tree iteratively, dropping it recursively could overflow the stack
+  let mut nodes = vec![root];
+  while let Some(mut node) = nodes.pop() {
+    if let TreeNode::Node(_, children, _, _) = &mut node {
+      nodes.append(children);
+    }
+  }
+}
+
+#[test]
+fn disposing_very_deep_tree_should_work() {
+  let cm = ColorMode::Off;
+  let mut root = leaf(cm).line().s("leaf").end().end();
+  for _ in 0..1_000_000 {
+    root = node(Color::Yellow, cm).line().s("node").end().child(root).end();
+  }
+  root.dispose();
+}
+
+#[test]
+fn editing_tree_should_work() {
+  let cm = ColorMode::Off;
+  let mut root = node(Color::Yellow, cm).line().s("suite").end().end();
+  assert!(root.push_child(leaf(cm).line().s("test b").end().end()).is_ok());
+  assert!(root.insert_child(0, leaf(cm).line().s("test a").end().end()).is_ok());
+  assert!(root.push_child(leaf(cm).line().s("test c").end().line().s("running").end().end()).is_ok());
+  assert_eq!(" suite\n ├─ test a\n ├─ test b\n └─ test c\n    running\n", root.to_string());
+
+  let test_c = root.child_mut(2).unwrap();
+  test_c.make_node(Color::Red, cm);
+  test_c.child_mut(0).unwrap().lines_mut().unwrap()[0] = Text::new(cm).s("failed");
+  assert!(test_c.push_child(leaf(cm).line().s("left: 1").end().end()).is_ok());
+  assert!(test_c.child_mut(1).unwrap().add_line(Text::new(cm).s("right: 2")).is_ok());
+  assert!(root.move_child(2, 0));
+  let removed = root.remove_child(2).unwrap();
+  assert_eq!("test b", removed.lines()[0].to_string());
+  assert!(root.remove_child(5).is_none());
+  let old = root.set_title(Text::new(cm).s("suite (1 failed)")).unwrap();
+  assert_eq!("suite", old.to_string());
+  assert_eq!(
+    " suite (1 failed)\n ├─ test c\n │  ├─ failed\n │  └─ left: 1\n │     right: 2\n └─ test a\n",
+    root.to_string()
+  );
+  assert!(root.child(1).unwrap().is_leaf());
+  assert_eq!(2, root.children().len());
+}
+
+#[test]
+fn editing_wrong_node_variant_should_not_panic() {
+  let cm = ColorMode::Off;
+  let mut root = node(Color::Yellow, cm).line().s("suite").end().end();
+  let mut test = leaf(cm).line().s("test").end().end();
+  let rejected = test.push_child(leaf(cm).line().s("pushed").end().end()).unwrap_err();
+  assert_eq!("pushed", rejected.lines()[0].to_string());
+  assert!(test.insert_child(0, leaf(cm).end()).is_err());
+  assert!(!test.move_child(0, 0));
+  assert_eq!("line", root.add_line(Text::new(cm).s("line")).unwrap_err().to_string());
+  assert_eq!(1, test.lines().len());
+  assert!(root.children().is_empty());
+  assert_eq!(
+    " suite
+",
+    root.to_string()
+  );
+}
+
+#[test]
+fn plain_string_should_not_depend_on_color_mode() {
+  let build = |cm: ColorMode| {
+    node(Color::Yellow, cm)
+      .line()
+      .bold()
+      .s("root")
+      .end()
+      .child(leaf(cm).line().red().s("\u{1b}[4mraw").end().end())
+      .end()
+  };
+  assert_eq!(" root\n └─ raw\n", build(ColorMode::On).to_plain_string());
+  assert_eq!(build(ColorMode::Off).to_plain_string(), build(ColorMode::On).to_plain_string());
+}
+
+#[test]
+fn destructuring_tree_by_value_should_work() {
+  match tree() {
+    TreeNode::Node(title, children, color, cm) => {
+      assert_eq!("root", title.plain());
+      assert_eq!(2, children.len());
+      assert_eq!((Color::Yellow, ColorMode::Off), (color, cm));
+    }
+    TreeNode::Leaf(_) => unreachable!(),
+  }
+}